@@ -0,0 +1,45 @@
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use ejmacroquad::entidades::EspecieDepredador;
+use ejmacroquad::sistemas::Simulacion;
+use ejmacroquad::sistemas::inicializacion::inicializar_depredadores;
+use ejmacroquad::sistemas::rendimiento::poblar_adultas;
+use ejmacroquad::utilidades::configuraciones::PASO_SIN_INTERFAZ;
+use ejmacroquad::utilidades::renderizado::{RendererNulo, dibujar_mundo};
+
+// ==================== BENCHMARK DEL PASO ====================
+// Mismo mundo que --rendimiento (presas adultas) con dos depredadores de cada especie;
+// cada iteracion parte de un mundo nuevo y avanza un paso, en serie y en paralelo
+fn mundo(presas: usize, paralelo: bool) -> Simulacion {
+    let mut sim = Simulacion::new(7);
+    sim.paralelo = paralelo;
+    poblar_adultas(&mut sim, presas);
+    sim.mundo.retener(|m, i| !m.cazas.tiene(i));
+    inicializar_depredadores(&mut sim.mundo, &EspecieDepredador::TODAS, EspecieDepredador::TODAS.len() * 2, &sim.terreno, &mut sim.rng);
+    sim
+}
+
+fn paso(c: &mut Criterion) {
+    let mut grupo = c.benchmark_group("paso");
+    for presas in [600, 2000] {
+        for paralelo in [false, true] {
+            let nombre = if paralelo { "paralelo" } else { "serial" };
+            grupo.bench_with_input(BenchmarkId::new(nombre, presas), &presas, |b, &presas| {
+                b.iter_batched(|| mundo(presas, paralelo), |mut sim| sim.paso(PASO_SIN_INTERFAZ), BatchSize::LargeInput)
+            });
+        }
+    }
+    grupo.finish();
+}
+
+// Un cuadro de la ventana sin GPU: el paso mas el recorrido del mundo que hace cada renderer
+fn cuadro(c: &mut Criterion) {
+    c.bench_function("cuadro/600", |b| {
+        b.iter_batched(|| mundo(600, true), |mut sim| {
+            sim.paso(PASO_SIN_INTERFAZ);
+            dibujar_mundo(&mut RendererNulo, &sim.terreno, &sim.mundo, sim.tiempo_acumulado);
+        }, BatchSize::LargeInput)
+    });
+}
+
+criterion_group!(benches, paso, cuadro);
+criterion_main!(benches);
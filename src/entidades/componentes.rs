@@ -0,0 +1,132 @@
+use crate::entidades::{Especie, EstadoSalud, EstrategiaCaza, Sexo, TipoCelda};
+use crate::entidades::mundo::Entidad;
+use crate::sistemas::bordes::desplazamiento;
+
+// ==================== COMPONENTES ====================
+// Datos sueltos que el Mundo guarda cada uno en su propia columna. Una entidad es la
+// combinacion de los que tiene (ver plantilla_presa y plantilla_depredador) y cada sistema
+// consulta solo los que usa; las especies (Especie, EspecieDepredador) tambien son componentes.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Posicion {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Velocidad {
+    pub vx: f32,
+    pub vy: f32,
+}
+
+impl Velocidad {
+    /// Velocidad de modulo `rapidez` desde `desde` hacia (tx, ty); sin cambio si ya esta ahi
+    pub fn hacia(&mut self, desde: Posicion, tx: f32, ty: f32, rapidez: f32) {
+        let (dx, dy) = desplazamiento(desde.x, desde.y, tx, ty);
+        let dist = (dx * dx + dy * dy).sqrt();
+        if dist > 0.0 {
+            self.vx = dx / dist * rapidez;
+            self.vy = dy / dist * rapidez;
+        }
+    }
+
+    pub fn detener(&mut self) {
+        self.vx = 0.0;
+        self.vy = 0.0;
+    }
+}
+
+// Radio del cuerpo: choques, dibujo y seleccion
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Cuerpo {
+    pub radio: f32,
+}
+
+// Como se desplaza: rapidez maxima, cuanto rinde en cada celda y actividad (dia, noche)
+#[derive(Clone, Copy, Debug)]
+pub struct Locomocion {
+    pub rapidez: f32,
+    pub factor_celda: fn(&TipoCelda) -> f32,
+    pub actividad: (f32, f32),
+}
+
+impl Locomocion {
+    pub fn factor(&self, celda: TipoCelda) -> f32 {
+        (self.factor_celda)(&celda)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Salud {
+    pub estado: EstadoSalud,
+    pub dias_enfermo: u32,
+    pub dias_inmune: u32,
+}
+
+impl Default for Salud {
+    fn default() -> Self {
+        Self { estado: EstadoSalud::Sano, dias_enfermo: 0, dias_inmune: 0 }
+    }
+}
+
+// Tiempo (s) que falta para volver a actuar: comer, atacar o reproducirse
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Espera {
+    pub restante: f32,
+}
+
+impl Espera {
+    pub fn avanzar(&mut self, dt: f32) {
+        self.restante = (self.restante - dt).max(0.0);
+    }
+}
+
+// Reserva de alimento de un depredador
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Dieta {
+    pub reserva: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reproduccion {
+    pub sexo: Sexo,
+    pub activa: bool, // modo_reproduccion: busca pareja
+    pub pareja: Option<Entidad>,
+}
+
+// Edad (dias) y peso, que sigue la curva de Gompertz de la especie
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Crecimiento {
+    pub edad: u32,
+    pub peso: f32,
+}
+
+// Estrategia, presa perseguida y presas que siguen tocandolo con el encuentro ya resuelto
+#[derive(Clone, Debug, PartialEq)]
+pub struct Caza {
+    pub estrategia: EstrategiaCaza,
+    pub objetivo: Option<Entidad>,
+    pub contactos: Vec<Entidad>,
+}
+
+// Bitacora del dia en curso (se reinicia con iniciar_dia); una entidad muerta sale de la
+// bitacora despues del dia en que murio
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bitacora {
+    pub reserva_inicio_dia: f32,
+    pub capturas_dia: [u32; 3],
+    pub biomasa_dia: f32,
+    pub distancia_dia: f32,
+    pub activa: bool,
+}
+
+impl Bitacora {
+    pub fn registrar_captura(&mut self, especie: Especie, biomasa: f32) {
+        self.capturas_dia[especie.indice()] += 1;
+        self.biomasa_dia += biomasa;
+    }
+
+    pub fn iniciar_dia(&mut self, reserva: f32, vivo: bool) {
+        *self = Self { reserva_inicio_dia: reserva, activa: vivo, ..Self::default() };
+    }
+}
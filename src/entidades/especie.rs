@@ -1,7 +1,6 @@
-use macroquad::prelude::*;
 
 use crate::{
-    utilidades::{GOMPERTZ_A_ARDILLA, GOMPERTZ_A_CONEJO, GOMPERTZ_A_RATON, GOMPERTZ_B_ARDILLA, GOMPERTZ_B_CONEJO, GOMPERTZ_B_RATON, GOMPERTZ_C_ARDILLA, GOMPERTZ_C_CONEJO, GOMPERTZ_C_RATON}, EDAD_MINIMA_REPRODUCCION_ARDILLA, EDAD_MINIMA_REPRODUCCION_CONEJO, EDAD_MINIMA_REPRODUCCION_RATON, EDAD_MINIMA_SACRIFICIO_ARDILLA, EDAD_MINIMA_SACRIFICIO_CONEJO, EDAD_MINIMA_SACRIFICIO_RATON, POBLACION_MAXIMA_ARDILLA, POBLACION_MAXIMA_CONEJO, POBLACION_MAXIMA_RATON, PROB_MACHO_ARDILLA, PROB_MACHO_CONEJO, PROB_MACHO_RATON
+    utilidades::{GOMPERTZ_A_ARDILLA, GOMPERTZ_A_CONEJO, GOMPERTZ_A_RATON, GOMPERTZ_B_ARDILLA, GOMPERTZ_B_CONEJO, GOMPERTZ_B_RATON, GOMPERTZ_C_ARDILLA, GOMPERTZ_C_CONEJO, GOMPERTZ_C_RATON}, EDAD_MINIMA_REPRODUCCION_ARDILLA, EDAD_MINIMA_REPRODUCCION_CONEJO, EDAD_MINIMA_REPRODUCCION_RATON, EDAD_MINIMA_SACRIFICIO_ARDILLA, EDAD_MINIMA_SACRIFICIO_CONEJO, EDAD_MINIMA_SACRIFICIO_RATON, POBLACION_MAXIMA_ARDILLA, POBLACION_MAXIMA_CONEJO, POBLACION_MAXIMA_RATON, PROB_MACHO_ARDILLA, VENTANA_REPRODUCCION_ARDILLA, ESTRATEGIA_ZORRO, ESTRATEGIA_LINCE, ESTRATEGIA_BUHO, ESTRATEGIA_COMADREJA, ESTRATEGIA_HALCON, ACTIVIDAD_ZORRO, ACTIVIDAD_LINCE, ACTIVIDAD_BUHO, ACTIVIDAD_COMADREJA, ACTIVIDAD_HALCON, ACTIVIDAD_ARDILLA, ACTIVIDAD_CONEJO, ACTIVIDAD_RATON, VENTANA_REPRODUCCION_CONEJO, VENTANA_REPRODUCCION_RATON, PROB_MACHO_CONEJO, PROB_MACHO_RATON
 };
 
 
@@ -33,11 +32,20 @@ impl EstadoSalud {
 pub enum Especie { Conejo, Raton, Ardilla }
 
 impl Especie {
-    pub fn color(&self) -> Color {
-        match self {
-            Especie::Conejo => BLUE,
-            Especie::Raton => GREEN,
-            Especie::Ardilla => ORANGE,
+    pub const TODAS: [Especie; 3] = [Especie::Conejo, Especie::Raton, Especie::Ardilla];
+
+    // Posicion en TODAS, para los contadores por especie
+    pub fn indice(&self) -> usize {
+        Especie::TODAS.iter().position(|e| e == self).unwrap()
+    }
+
+    // Acepta el nombre con o sin tilde, sin importar mayusculas
+    pub fn desde_nombre(nombre: &str) -> Option<Especie> {
+        match nombre.to_lowercase().as_str() {
+            "conejo" => Some(Especie::Conejo),
+            "raton" | "ratón" => Some(Especie::Raton),
+            "ardilla" => Some(Especie::Ardilla),
+            _ => None,
         }
     }
 
@@ -58,6 +66,12 @@ impl Especie {
         }
     }
 
+    // Peso segun la curva de Gompertz para una edad en dias
+    pub fn peso_gompertz(&self, edad: u32) -> f32 {
+        let (a, b, c) = self.gompertz_params();
+        a * (-b * f32::exp(-c * edad as f32)).exp()
+    }
+
     pub fn probabilidad_macho(&self) -> f32 {
         match self {
             Especie::Conejo => PROB_MACHO_CONEJO,
@@ -89,4 +103,121 @@ impl Especie {
             Especie::Ardilla => EDAD_MINIMA_REPRODUCCION_ARDILLA,
         }
     }
+
+    // Nivel de actividad (dia, noche)
+    pub fn actividad(&self) -> (f32, f32) {
+        match self {
+            Especie::Conejo => ACTIVIDAD_CONEJO,
+            Especie::Raton => ACTIVIDAD_RATON,
+            Especie::Ardilla => ACTIVIDAD_ARDILLA,
+        }
+    }
+
+    pub fn ventana_reproduccion(&self) -> (f32, f32) {
+        match self {
+            Especie::Conejo => VENTANA_REPRODUCCION_CONEJO,
+            Especie::Raton => VENTANA_REPRODUCCION_RATON,
+            Especie::Ardilla => VENTANA_REPRODUCCION_ARDILLA,
+        }
+    }
+}
+
+// ==================== DEPREDADORES ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EstrategiaCaza {
+    MasPesada,      // la presa mas pesada (a igual peso, la mas cercana)
+    MasCercana,     // la presa visible mas cercana
+    ForrajeoOptimo, // maximiza peso / distancia
+    Emboscada,      // espera quieto hasta que una presa entra en RADIO_EMBOSCADA
+    Persecucion,    // apunta a donde estara la presa segun su velocidad
+}
+
+impl EstrategiaCaza {
+    pub const TODAS: [EstrategiaCaza; 5] = [
+        EstrategiaCaza::MasPesada,
+        EstrategiaCaza::MasCercana,
+        EstrategiaCaza::ForrajeoOptimo,
+        EstrategiaCaza::Emboscada,
+        EstrategiaCaza::Persecucion,
+    ];
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            EstrategiaCaza::MasPesada => "Más pesada",
+            EstrategiaCaza::MasCercana => "Más cercana",
+            EstrategiaCaza::ForrajeoOptimo => "Forrajeo óptimo",
+            EstrategiaCaza::Emboscada => "Emboscada",
+            EstrategiaCaza::Persecucion => "Persecución",
+        }
+    }
+
+    // Posicion en TODAS, para los contadores de capturas
+    pub fn indice(&self) -> usize {
+        EstrategiaCaza::TODAS.iter().position(|e| e == self).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EspecieDepredador { Zorro, Lince, Buho, Comadreja, Halcon }
+
+impl EspecieDepredador {
+    pub const TODAS: [EspecieDepredador; 5] = [
+        EspecieDepredador::Zorro,
+        EspecieDepredador::Lince,
+        EspecieDepredador::Buho,
+        EspecieDepredador::Comadreja,
+        EspecieDepredador::Halcon,
+    ];
+
+    pub fn siguiente(&self) -> EspecieDepredador {
+        match self {
+            EspecieDepredador::Zorro => EspecieDepredador::Lince,
+            EspecieDepredador::Lince => EspecieDepredador::Buho,
+            EspecieDepredador::Buho => EspecieDepredador::Comadreja,
+            EspecieDepredador::Comadreja => EspecieDepredador::Halcon,
+            EspecieDepredador::Halcon => EspecieDepredador::Zorro,
+        }
+    }
+
+    pub fn desde_nombre(nombre: &str) -> Option<EspecieDepredador> {
+        match nombre.to_lowercase().as_str() {
+            "zorro" => Some(EspecieDepredador::Zorro),
+            "lince" => Some(EspecieDepredador::Lince),
+            "buho" | "búho" => Some(EspecieDepredador::Buho),
+            "comadreja" => Some(EspecieDepredador::Comadreja),
+            "halcon" | "halcón" => Some(EspecieDepredador::Halcon),
+            _ => None,
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            EspecieDepredador::Zorro => "Zorro",
+            EspecieDepredador::Lince => "Lince",
+            EspecieDepredador::Buho => "Búho",
+            EspecieDepredador::Comadreja => "Comadreja",
+            EspecieDepredador::Halcon => "Halcón",
+        }
+    }
+
+    pub fn estrategia(&self) -> EstrategiaCaza {
+        match self {
+            EspecieDepredador::Zorro => ESTRATEGIA_ZORRO,
+            EspecieDepredador::Lince => ESTRATEGIA_LINCE,
+            EspecieDepredador::Buho => ESTRATEGIA_BUHO,
+            EspecieDepredador::Comadreja => ESTRATEGIA_COMADREJA,
+            EspecieDepredador::Halcon => ESTRATEGIA_HALCON,
+        }
+    }
+
+    // Nivel de actividad (dia, noche)
+    pub fn actividad(&self) -> (f32, f32) {
+        match self {
+            EspecieDepredador::Zorro => ACTIVIDAD_ZORRO,
+            EspecieDepredador::Lince => ACTIVIDAD_LINCE,
+            EspecieDepredador::Buho => ACTIVIDAD_BUHO,
+            EspecieDepredador::Comadreja => ACTIVIDAD_COMADREJA,
+            EspecieDepredador::Halcon => ACTIVIDAD_HALCON,
+        }
+    }
 }
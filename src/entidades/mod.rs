@@ -1,9 +1,12 @@
-pub mod organismo;
+pub mod componentes;
+pub mod mundo;
 pub mod presa;
 pub mod depredador;
 pub mod especie;
+pub mod terreno;
 
-pub use organismo::Organismo;
-pub use presa::Presa;
-pub use depredador::Depredador;
-pub use especie::{Especie, Sexo, EstadoSalud};
+pub use mundo::{Mundo, Entidad, Plantilla, Columna};
+pub use presa::plantilla_presa;
+pub use depredador::plantilla_depredador;
+pub use especie::{Especie, Sexo, EstadoSalud, EspecieDepredador, EstrategiaCaza};
+pub use terreno::{Terreno, TipoCelda};
@@ -0,0 +1,205 @@
+use std::ops::{Index, IndexMut};
+
+use crate::entidades::{Especie, EspecieDepredador};
+use crate::entidades::componentes::{Posicion, Velocidad, Cuerpo, Locomocion, Salud, Espera, Dieta, Reproduccion,
+                                    Crecimiento, Caza, Bitacora};
+
+// ==================== MUNDO ====================
+// Entidades guardadas por componente: cada entidad es una fila y cada componente tiene su propia
+// columna, con None en las filas que no lo llevan. Los sistemas consultan las filas que tienen lo
+// que necesitan, asi que un tipo de organismo nuevo (carroñero, planta) es solo otra Plantilla.
+// Las filas quedan en orden de creacion (retener no las reordena), por eso los ids crecen con la fila
+
+/// Identificador estable de una entidad; su fila cambia cuando se retiran las anteriores
+pub type Entidad = u32;
+
+#[derive(Clone, Debug)]
+pub struct Columna<T> {
+    valores: Vec<Option<T>>,
+}
+
+impl<T> Default for Columna<T> {
+    fn default() -> Self {
+        Self { valores: Vec::new() }
+    }
+}
+
+impl<T> Columna<T> {
+    pub fn get(&self, fila: usize) -> Option<&T> {
+        self.valores.get(fila).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, fila: usize) -> Option<&mut T> {
+        self.valores.get_mut(fila).and_then(Option::as_mut)
+    }
+
+    pub fn tiene(&self, fila: usize) -> bool {
+        self.get(fila).is_some()
+    }
+
+    /// Filas que tienen el componente, en orden
+    pub fn filas(&self) -> impl Iterator<Item = usize> + '_ {
+        self.iter().map(|(fila, _)| fila)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.valores.iter().enumerate().filter_map(|(fila, v)| Some((fila, v.as_ref()?)))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.valores.iter_mut().enumerate().filter_map(|(fila, v)| Some((fila, v.as_mut()?)))
+    }
+}
+
+// Una consulta ya sabe que la fila tiene el componente: si falta es un error de la plantilla
+impl<T> Index<usize> for Columna<T> {
+    type Output = T;
+
+    fn index(&self, fila: usize) -> &T {
+        self.get(fila).expect("la entidad no tiene el componente")
+    }
+}
+
+impl<T> IndexMut<usize> for Columna<T> {
+    fn index_mut(&mut self, fila: usize) -> &mut T {
+        self.get_mut(fila).expect("la entidad no tiene el componente")
+    }
+}
+
+fn retener_filas<T>(valores: &mut Vec<T>, conservar: &[bool]) {
+    let mut conservar = conservar.iter();
+    valores.retain(|_| *conservar.next().unwrap());
+}
+
+/// Componentes con los que nace una entidad; los que quedan en None no los tiene
+#[derive(Clone, Debug, Default)]
+pub struct Plantilla {
+    pub posicion: Option<Posicion>,
+    pub velocidad: Option<Velocidad>,
+    pub cuerpo: Option<Cuerpo>,
+    pub locomocion: Option<Locomocion>,
+    pub salud: Option<Salud>,
+    pub espera: Option<Espera>,
+    pub dieta: Option<Dieta>,
+    pub reproduccion: Option<Reproduccion>,
+    pub crecimiento: Option<Crecimiento>,
+    pub caza: Option<Caza>,
+    pub bitacora: Option<Bitacora>,
+    pub especie: Option<Especie>,
+    pub especie_depredador: Option<EspecieDepredador>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Mundo {
+    siguiente_id: Entidad,
+    pub(crate) ids: Vec<Entidad>,
+    pub(crate) vivos: Vec<bool>,
+    pub posiciones: Columna<Posicion>,
+    pub velocidades: Columna<Velocidad>,
+    pub cuerpos: Columna<Cuerpo>,
+    pub locomociones: Columna<Locomocion>,
+    pub salud: Columna<Salud>,
+    pub esperas: Columna<Espera>,
+    pub dietas: Columna<Dieta>,
+    pub reproducciones: Columna<Reproduccion>,
+    pub crecimientos: Columna<Crecimiento>,
+    pub cazas: Columna<Caza>,
+    pub bitacoras: Columna<Bitacora>,
+    pub especies: Columna<Especie>,
+    pub especies_depredador: Columna<EspecieDepredador>,
+}
+
+impl Mundo {
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Agrega una fila al final con los componentes de la plantilla y devuelve su id
+    pub fn crear(&mut self, p: Plantilla) -> Entidad {
+        let id = self.siguiente_id;
+        self.siguiente_id += 1;
+        self.ids.push(id);
+        self.vivos.push(true);
+        self.posiciones.valores.push(p.posicion);
+        self.velocidades.valores.push(p.velocidad);
+        self.cuerpos.valores.push(p.cuerpo);
+        self.locomociones.valores.push(p.locomocion);
+        self.salud.valores.push(p.salud);
+        self.esperas.valores.push(p.espera);
+        self.dietas.valores.push(p.dieta);
+        self.reproducciones.valores.push(p.reproduccion);
+        self.crecimientos.valores.push(p.crecimiento);
+        self.cazas.valores.push(p.caza);
+        self.bitacoras.valores.push(p.bitacora);
+        self.especies.valores.push(p.especie);
+        self.especies_depredador.valores.push(p.especie_depredador);
+        id
+    }
+
+    pub fn id(&self, fila: usize) -> Entidad {
+        self.ids[fila]
+    }
+
+    /// Fila actual de la entidad, si sigue en el mundo
+    pub fn fila(&self, id: Entidad) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    pub fn esta_vivo(&self, fila: usize) -> bool {
+        self.vivos[fila]
+    }
+
+    pub fn matar(&mut self, fila: usize) {
+        self.vivos[fila] = false;
+    }
+
+    /// Retira las filas para las que `conservar` da false; las demas mantienen su orden
+    pub fn retener(&mut self, conservar: impl Fn(&Mundo, usize) -> bool) {
+        let mascara: Vec<bool> = (0..self.len()).map(|fila| conservar(self, fila)).collect();
+        retener_filas(&mut self.ids, &mascara);
+        retener_filas(&mut self.vivos, &mascara);
+        retener_filas(&mut self.posiciones.valores, &mascara);
+        retener_filas(&mut self.velocidades.valores, &mascara);
+        retener_filas(&mut self.cuerpos.valores, &mascara);
+        retener_filas(&mut self.locomociones.valores, &mascara);
+        retener_filas(&mut self.salud.valores, &mascara);
+        retener_filas(&mut self.esperas.valores, &mascara);
+        retener_filas(&mut self.dietas.valores, &mascara);
+        retener_filas(&mut self.reproducciones.valores, &mascara);
+        retener_filas(&mut self.crecimientos.valores, &mascara);
+        retener_filas(&mut self.cazas.valores, &mascara);
+        retener_filas(&mut self.bitacoras.valores, &mascara);
+        retener_filas(&mut self.especies.valores, &mascara);
+        retener_filas(&mut self.especies_depredador.valores, &mascara);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn en(x: f32) -> Plantilla {
+        Plantilla { posicion: Some(Posicion { x, y: 0.0 }), ..Plantilla::default() }
+    }
+
+    #[test]
+    fn retener_conserva_el_orden_y_los_ids() {
+        let mut mundo = Mundo::default();
+        for x in 0..5 {
+            mundo.crear(en(x as f32));
+        }
+        mundo.matar(1);
+        mundo.matar(3);
+        mundo.retener(|m, i| m.esta_vivo(i));
+        assert_eq!(mundo.ids, vec![0, 2, 4]);
+        assert_eq!(mundo.posiciones.iter().map(|(_, p)| p.x).collect::<Vec<_>>(), vec![0.0, 2.0, 4.0]);
+        assert_eq!((mundo.fila(2), mundo.fila(3), mundo.fila(4)), (Some(1), None, Some(2)));
+        // Los ids no se reusan y una fila nueva solo tiene los componentes de su plantilla
+        assert_eq!(mundo.crear(en(5.0)), 5);
+        assert!(mundo.posiciones.tiene(3) && !mundo.velocidades.tiene(3));
+    }
+}
@@ -0,0 +1,207 @@
+use std::fs;
+
+use crate::utilidades::configuraciones::{ANCHO_MUNDO, ALTO_MUNDO, PALETA_TERRENO};
+
+// ==================== TIPOS DE CELDA ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TipoCelda { Campo, Bosque, Agua, Rocas, Madriguera }
+
+impl TipoCelda {
+    // Formato de texto: '.' campo, 'B' bosque, '~' agua, '#' rocas, 'M' madriguera
+    pub fn desde_caracter(c: char) -> Option<TipoCelda> {
+        match c {
+            '.' => Some(TipoCelda::Campo),
+            'B' => Some(TipoCelda::Bosque),
+            '~' => Some(TipoCelda::Agua),
+            '#' => Some(TipoCelda::Rocas),
+            'M' => Some(TipoCelda::Madriguera),
+            _ => None,
+        }
+    }
+
+    // Formato PNG: el tipo cuyo color en PALETA_TERRENO es mas parecido al pixel
+    pub fn desde_rgb(rgb: [u8; 3]) -> TipoCelda {
+        let distancia = |color: &[u8; 3]| color.iter().zip(rgb).map(|(&a, b)| (a as i32 - b as i32).pow(2)).sum::<i32>();
+        PALETA_TERRENO.iter().min_by_key(|(_, color)| distancia(color)).map(|(tipo, _)| *tipo).unwrap()
+    }
+
+    // Multiplicadores de velocidad; 0.0 significa intransitable
+    pub fn factor_velocidad_presa(&self) -> f32 {
+        match self {
+            TipoCelda::Campo => 1.0,
+            TipoCelda::Bosque => 0.8,
+            TipoCelda::Agua => 0.3,
+            TipoCelda::Rocas => 0.0,
+            TipoCelda::Madriguera => 0.6,
+        }
+    }
+
+    pub fn factor_velocidad_depredador(&self) -> f32 {
+        match self {
+            TipoCelda::Campo => 1.0,
+            TipoCelda::Bosque => 0.6,
+            TipoCelda::Agua => 0.5,
+            TipoCelda::Rocas => 0.0,
+            TipoCelda::Madriguera => 0.8,
+        }
+    }
+
+    // Fraccion del radio de vision del depredador con la que se ve a una presa en esta celda
+    pub fn visibilidad(&self) -> f32 {
+        match self {
+            TipoCelda::Campo => 1.0,
+            TipoCelda::Bosque => 0.5,
+            TipoCelda::Agua => 1.0,
+            TipoCelda::Rocas => 0.8,
+            TipoCelda::Madriguera => 0.0,
+        }
+    }
+
+    // Fraccion del crecimiento diario de peso que logra una presa que pasta aqui
+    pub fn valor_pastoreo(&self) -> f32 {
+        match self {
+            TipoCelda::Campo => 1.0,
+            TipoCelda::Bosque => 0.7,
+            TipoCelda::Agua => 0.0,
+            TipoCelda::Rocas => 0.0,
+            TipoCelda::Madriguera => 0.3,
+        }
+    }
+}
+
+// ==================== TERRENO ====================
+#[derive(Clone, Debug)]
+pub struct Terreno {
+    columnas: usize,
+    filas: usize,
+    celdas: Vec<TipoCelda>,
+}
+
+impl Terreno {
+    /// Mundo sin obstaculos: una sola celda de campo abierto
+    pub fn abierto() -> Self {
+        Self { columnas: 1, filas: 1, celdas: vec![TipoCelda::Campo] }
+    }
+
+    /// Carga el mapa si el archivo existe; si no existe o es invalido usa campo abierto
+    pub fn cargar_o_abierto(ruta: &str) -> Terreno {
+        if !std::path::Path::new(ruta).exists() {
+            return Terreno::abierto();
+        }
+        Terreno::cargar(ruta).unwrap_or_else(|e| {
+            eprintln!("Error cargando terreno {}: {}", ruta, e);
+            Terreno::abierto()
+        })
+    }
+
+    /// Carga un mapa desde PNG (por extension) o desde una grilla de texto
+    pub fn cargar(ruta: &str) -> Result<Terreno, String> {
+        if ruta.to_lowercase().ends_with(".png") {
+            let archivo = fs::File::open(ruta).map_err(|e| e.to_string())?;
+            let mut decodificador = png::Decoder::new(archivo);
+            // Paleta, gris y 16 bits pasan a 8 bits por canal
+            decodificador.set_transformations(png::Transformations::normalize_to_color8());
+            let mut lector = decodificador.read_info().map_err(|e| e.to_string())?;
+            let mut pixeles = vec![0; lector.output_buffer_size()];
+            let info = lector.next_frame(&mut pixeles).map_err(|e| e.to_string())?;
+            let canales = info.color_type.samples();
+            let (columnas, filas) = (info.width as usize, info.height as usize);
+            let mut celdas = Vec::with_capacity(columnas * filas);
+            for fila in pixeles[..info.buffer_size()].chunks(info.line_size) {
+                for p in fila.chunks(canales).take(columnas) {
+                    // En gris (con o sin alfa) el primer canal es el valor
+                    let (r, g, b) = if canales >= 3 { (p[0], p[1], p[2]) } else { (p[0], p[0], p[0]) };
+                    celdas.push(TipoCelda::desde_rgb([r, g, b]));
+                }
+            }
+            Terreno::desde_celdas(columnas, filas, celdas)
+        } else {
+            let texto = fs::read_to_string(ruta).map_err(|e| e.to_string())?;
+            Terreno::desde_texto(&texto)
+        }
+    }
+
+    pub fn desde_texto(texto: &str) -> Result<Terreno, String> {
+        let lineas: Vec<&str> = texto.lines().map(|l| l.trim_end()).filter(|l| !l.is_empty()).collect();
+        let columnas = lineas.first().map_or(0, |l| l.chars().count());
+        let mut celdas = Vec::with_capacity(columnas * lineas.len());
+        for (fila, linea) in lineas.iter().enumerate() {
+            if linea.chars().count() != columnas {
+                return Err(format!("fila {} tiene {} columnas, se esperaban {}", fila + 1, linea.chars().count(), columnas));
+            }
+            for c in linea.chars() {
+                celdas.push(TipoCelda::desde_caracter(c).ok_or(format!("caracter de terreno desconocido '{}' en fila {}", c, fila + 1))?);
+            }
+        }
+        Terreno::desde_celdas(columnas, lineas.len(), celdas)
+    }
+
+    fn desde_celdas(columnas: usize, filas: usize, celdas: Vec<TipoCelda>) -> Result<Terreno, String> {
+        if columnas == 0 || filas == 0 {
+            return Err("el mapa de terreno esta vacio".to_string());
+        }
+        Ok(Terreno { columnas, filas, celdas })
+    }
+
+    // Indices de celda para una posicion del mundo (las posiciones fuera se recortan al borde)
+    fn indice(&self, x: f32, y: f32) -> (usize, usize) {
+        let c = ((x / ANCHO_MUNDO * self.columnas as f32).floor().max(0.0) as usize).min(self.columnas - 1);
+        let f = ((y / ALTO_MUNDO * self.filas as f32).floor().max(0.0) as usize).min(self.filas - 1);
+        (c, f)
+    }
+
+    pub fn celda_en(&self, x: f32, y: f32) -> TipoCelda {
+        let (c, f) = self.indice(x, y);
+        self.celdas[f * self.columnas + c]
+    }
+
+    pub fn columnas(&self) -> usize { self.columnas }
+    pub fn filas(&self) -> usize { self.filas }
+    pub fn celda(&self, columna: usize, fila: usize) -> TipoCelda { self.celdas[fila * self.columnas + columna] }
+
+    /// Si la celda de la posicion es agua o esta a `desborde` celdas o menos de una
+    pub fn inundable(&self, x: f32, y: f32, desborde: usize) -> bool {
+        let (c, f) = self.indice(x, y);
+        let columnas = c.saturating_sub(desborde)..=(c + desborde).min(self.columnas - 1);
+        let filas = f.saturating_sub(desborde)..=(f + desborde).min(self.filas - 1);
+        filas.flat_map(|fi| columnas.clone().map(move |ci| (ci, fi)))
+            .any(|(ci, fi)| self.celda(ci, fi) == TipoCelda::Agua)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Un mapa exportado con la paleta de los renderers se vuelve a cargar igual, en RGB y en gris
+    #[test]
+    fn png_con_la_paleta_se_carga_igual() {
+        let tipos = [TipoCelda::Campo, TipoCelda::Bosque, TipoCelda::Agua, TipoCelda::Rocas, TipoCelda::Madriguera];
+        let ruta = std::env::temp_dir().join(format!("terreno_prueba_{}.png", std::process::id()));
+        let mut pixeles = Vec::new();
+        for t in tipos.iter().chain(tipos.iter().rev()) {
+            pixeles.extend(PALETA_TERRENO.iter().find(|(tipo, _)| tipo == t).unwrap().1);
+        }
+        let mut codificador = png::Encoder::new(fs::File::create(&ruta).unwrap(), 5, 2);
+        codificador.set_color(png::ColorType::Rgb);
+        codificador.set_depth(png::BitDepth::Eight);
+        codificador.write_header().unwrap().write_image_data(&pixeles).unwrap();
+
+        let terreno = Terreno::cargar(ruta.to_str().unwrap()).unwrap();
+        assert_eq!((terreno.columnas(), terreno.filas()), (5, 2));
+        for (i, t) in tipos.iter().enumerate() {
+            assert_eq!(terreno.celda(i, 0), *t);
+            assert_eq!(terreno.celda(4 - i, 1), *t);
+        }
+
+        // En gris el valor va a los tres canales: blanco queda campo y 120 es el gris de las rocas
+        let mut codificador = png::Encoder::new(fs::File::create(&ruta).unwrap(), 2, 1);
+        codificador.set_color(png::ColorType::Grayscale);
+        codificador.set_depth(png::BitDepth::Eight);
+        codificador.write_header().unwrap().write_image_data(&[255, 120]).unwrap();
+        let terreno = Terreno::cargar(ruta.to_str().unwrap()).unwrap();
+        assert_eq!(terreno.celda(0, 0), TipoCelda::Campo);
+        assert_eq!(terreno.celda(1, 0), TipoCelda::Rocas);
+        let _ = fs::remove_file(&ruta);
+    }
+}
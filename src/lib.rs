@@ -0,0 +1,7 @@
+// Nucleo de la simulacion sin ventana: lo usan el binario y los benchmarks
+pub mod entidades;
+pub mod sistemas;
+pub mod utilidades;
+
+// Las entidades importan las constantes desde la raiz del crate
+use utilidades::*;
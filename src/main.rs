@@ -1,84 +1,117 @@
-use ::rand::thread_rng;
 use macroquad::prelude::*;
 
-mod entidades;
-mod sistemas;
+use ejmacroquad::{entidades, sistemas, utilidades};
+
+mod sin_interfaz;
+mod terminal;
 mod ui {
     pub mod interfaz;
+    pub mod plano_fase;
+    pub mod inspector;
+    pub mod camara;
+    pub mod serie_tiempo;
+    pub mod manejo;
+    pub mod densidad;
+    pub mod grabacion;
+    pub mod renderizado;
 }
-mod utilidades;
 
 // Traer lo necesario desde los re-exports
-use crate::entidades::{Presa, Depredador, Especie, Organismo};
-use crate::sistemas::*;
+use crate::sistemas::Simulacion;
 use crate::utilidades::*;
 use ui::interfaz::dibujar_ui;
+use ui::plano_fase::PanelFase;
+use ui::inspector::Inspector;
+use ui::camara::Camara;
+use ui::serie_tiempo::PanelSerie;
+use ui::manejo::PanelManejo;
+use ui::densidad::PanelDensidad;
+use ui::grabacion::PanelGrabacion;
+use ui::renderizado::RendererMacroquad;
+use crate::utilidades::renderizado::{Renderer, vistas};
 
 
 // ==================== MAIN ====================
-#[macroquad::main("Presa-Depredador")]
-async fn main() {
-    let mut rng = thread_rng();
-    let especies = [Especie::Conejo, Especie::Raton, Especie::Ardilla];
-
-    let mut presas: Vec<Presa> = inicializar_presas(&especies, 50, &mut rng);
-    let mut depredadores: Vec<Depredador> = inicializar_depredadores(1, &mut rng);
-
-    let mut dias: u32 = 0;
-    let mut tiempo_acumulado: f32 = 0.0;
+// Sin argumentos abre la ventana; con argumentos corre sin interfaz (ver sin_interfaz.rs)
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(e) = sin_interfaz::ejecutar(&args) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    macroquad::Window::new("Presa-Depredador", ventana());
+}
 
-    // Contadores diarios
-    let mut muertes_por_predacion_diarias = 0;
-    let mut muertes_por_enfermedad_diarias = 0;
-    let mut nuevos_infectados_diarios = 0;
-    let mut recuperaciones_diarias = 0;
-    let mut reproducciones_diarias = 0;
+async fn ventana() {
+    let mut sim = Simulacion::new(::rand::random());
+    // Los reportes se escriben dia a dia; cerrar la ventana tambien guarda lo pendiente
+    if let Err(e) = sim.abrir_salida(FORMATO_REPORTES) {
+        eprintln!("Error abriendo reportes: {}", e);
+    }
+    prevent_quit();
 
-    let mut reportes: Vec<EstadisticasDiarias> = Vec::new();
+    let mut panel_fase = PanelFase::new();
+    let mut inspector = Inspector::new();
+    let mut camara = Camara::new();
+    let mut panel_serie = PanelSerie::new();
+    let mut panel_manejo = PanelManejo::new();
+    let mut panel_densidad = PanelDensidad::new();
+    let mut panel_grabacion = PanelGrabacion::new();
+    let mut renderer = RendererMacroquad;
 
     loop {
         clear_background(LIGHTGRAY);
-        tiempo_acumulado += get_frame_time();
-        if tiempo_acumulado >= DURACION_DIA {
-            dias += 1;
-            tiempo_acumulado = 0.0;
-            // Procesos diarios
-            resetear_contadores_diarios(&mut muertes_por_predacion_diarias,&mut muertes_por_enfermedad_diarias,&mut nuevos_infectados_diarios,&mut recuperaciones_diarias);
-            actualizar_presas_diarias(&mut presas);
-            procesar_enfermedad_presas(&mut presas, &mut rng,&mut nuevos_infectados_diarios,&mut recuperaciones_diarias,&mut muertes_por_enfermedad_diarias);
-            procesar_dietas_depredadores(&mut depredadores, dias);
-            // Guardar reporte diario
-            reportes.push(compilar_reporte_diario(&presas, &depredadores,muertes_por_predacion_diarias,muertes_por_enfermedad_diarias,nuevos_infectados_diarios,recuperaciones_diarias,reproducciones_diarias,dias));
-            reproducciones_diarias = 0;
+        sim.paso(get_frame_time());
+        let periodo = sim.periodo();
+        // Camara sobre el mundo
+        camara.manejar_entrada(inspector.posicion(&sim.mundo));
+        camara.activar();
+        renderer.terreno(&sim.terreno);
+        panel_densidad.manejar_teclas();
+        panel_densidad.dibujar_mapa(&sim.densidad);
+        for v in vistas(&sim.mundo) {
+            renderer.organismo(&v);
+        }
+        // Inspeccion del organismo seleccionado
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let (mx, my) = mouse_position();
+            if !camara.click_en_minimapa(mx, my) {
+                let (wx, wy) = camara.pantalla_a_mundo(mx, my);
+                inspector.manejar_click(wx, wy, &sim.mundo);
+            }
         }
-        // ==================== Movimiento inteligente ====================
-        movimiento_presas(&mut presas, &mut rng);
-        depredadores_buscar_presas(&mut depredadores, &presas);
-        // Actualizar y dibujar organismos
-        actualizar_y_dibujar(&mut presas);
-        actualizar_y_dibujar(&mut depredadores);
-        // Reproducción
-        reproduccion(&mut presas, &especies, &mut reproducciones_diarias);
-        // Depredadores comen
-        depredadores_comer(&mut depredadores, &mut presas, &mut muertes_por_predacion_diarias);
-        // Limpiar presas muertas
-        presas.retain(|p| p.esta_vivo());
+        // Intervenciones por teclado
+        let (mx, my) = mouse_position();
+        if let Some(intervencion) = panel_manejo.intervencion_pedida(camara.pantalla_a_mundo(mx, my)) {
+            sim.intervenir(intervencion, "tecla");
+        }
+        inspector.actualizar(&sim.mundo);
+        inspector.dibujar_resaltado(&sim.mundo);
+        renderer.noche(sim.tiempo_acumulado);
         // Interfaz
-        dibujar_ui(dias, &presas, &depredadores, &reportes);
+        set_default_camera();
+        camara.dibujar_minimapa(&sim.mundo);
+        panel_fase.manejar_teclas(&sim.reportes);
+        panel_fase.dibujar(&sim.reportes);
+        panel_serie.manejar_teclas();
+        panel_serie.dibujar(&sim.reportes, sim.planificador.registro());
+        inspector.dibujar_panel(&sim.mundo);
+        panel_manejo.dibujar();
+        panel_densidad.dibujar_leyenda(&sim.densidad);
+        panel_grabacion.manejar_teclas();
+        panel_grabacion.capturar();
+        panel_grabacion.dibujar_indicador();
+        dibujar_ui(sim.dias, periodo, &sim.mundo);
+        // Esc o cerrar la ventana: cerrar la salida de reportes y terminar
+        if is_key_pressed(KeyCode::Escape) || is_quit_requested() {
+            sim.cerrar_salida();
+            panel_grabacion.finalizar();
+            println!("Reportes guardados en {}", FORMATO_REPORTES.rutas().join(", "));
+            std::process::exit(0); // cerrar limpio
+        }
         next_frame().await;
     }
 }
-
-// ==================== FUNCIONES AUXILIARES ====================
-fn actualizar_y_dibujar<T: Organismo>(organismos: &mut [T]) {
-    for o in organismos.iter_mut() {
-        o.actualizar();
-        o.dibujar();
-    }
-}
-
-
-
-
-
-
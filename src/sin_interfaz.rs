@@ -0,0 +1,379 @@
+use crate::entidades::EspecieDepredador;
+use crate::sistemas::Simulacion;
+use crate::terminal;
+use crate::sistemas::respuesta_funcional::medir_respuesta_funcional;
+use crate::sistemas::rendimiento::medir_rendimiento;
+use crate::sistemas::campo_medio::simular_campo_medio;
+use crate::utilidades::configuraciones::*;
+use crate::utilidades::csv::{guardar_reportes_csv, cargar_reportes_csv, guardar_comparacion_csv};
+use crate::utilidades::salidas::FormatoReportes;
+use crate::utilidades::densidad::{FormatoDensidad, MapaDensidad};
+use crate::utilidades::grabacion::{FormatoGrabacion, Grabadora};
+use crate::utilidades::plano_fase::{EjePresas, EjeDepredadores, guardar_plano_fase_svg};
+use crate::utilidades::renderizado::{rasterizar_mundo, dibujar_mundo, RendererSvg};
+use crate::utilidades::holling::{ModeloHolling, ajustar_holling, mejor_ajuste, guardar_respuesta_csv, guardar_ajustes_csv};
+use crate::utilidades::analisis::{analizar_reportes, guardar_resumen_json};
+use crate::utilidades::lotka_volterra::{ModeloPoblacion, ajustar_poblacion, guardar_ajuste_csv, guardar_parametros_csv};
+
+const AYUDA: &str = "Uso: ejmacroquad [opciones]   (sin opciones abre la ventana)
+  --dias <n>                  dias a simular sin interfaz
+  --semilla <n>               semilla del generador aleatorio
+  --formato <f>               csv, json, jsonl o sqlite para los reportes de la corrida
+  --piramide                  ademas registra la piramide de edades por especie y sexo cada dia
+  --densidad <csv|pgm>        exporta cada dia los mapas de densidad por capa a densidad/
+    --rejilla <CxF>           columnas y filas de la rejilla (por defecto 40x30)
+    --ventana-densidad <n>    dias que acumula cada mapa (por defecto 7)
+  --grabar <png|gif>          dibuja el mundo sin ventana y guarda cuadros en grabacion/ o grabacion.gif
+    --cada <k>                uno de cada k pasos (60 pasos por dia; por defecto 10)
+    --escala <x>              tamaño de la imagen respecto del mundo (por defecto 0.5)
+  --svg <ruta>                guarda el mundo del ultimo paso como imagen SVG
+  --fase <ruta>               guarda el plano de fase (presas totales vs depredadores) como SVG
+  --tui                       muestra la simulacion en la terminal (sin pantalla grafica)
+  --serial                    corre el paso en un solo hilo (el resultado es el mismo)
+  --rendimiento <n>           mide el paso con n presas en serie y con 1, 2, 4... hilos
+  --respuesta-funcional       mide capturas por depredador y dia a densidad fija y ajusta Holling I/II/III
+    --presas <n,n,...>        cantidades de presas a probar
+    --depredadores <n>        depredadores en cada corrida
+    --especie <nombre>        zorro, lince, buho, comadreja o halcon
+  --ajustar <reportes.csv>    ajusta Lotka-Volterra y Rosenzweig-MacArthur a una serie guardada
+    --modelo <lv|rm>          ajusta solo uno de los dos modelos
+  --edo                       integra el modelo de campo medio (EDO) y guarda reportes_edo.csv
+    --comparar <reportes.csv> ademas guarda comparacion_edo.csv con las curvas del modelo de agentes
+  --analizar <reportes.csv>   periodos, amplitudes, extinciones y desfase presa-depredador en JSON
+    --salida <ruta>           archivo del resumen (por defecto resumen.json)
+  --ayuda                     muestra esta ayuda";
+
+// ==================== OPCIONES ====================
+struct Opciones {
+    dias: Option<u32>,
+    semilla: u64,
+    formato: FormatoReportes,
+    piramide: bool,
+    densidad: Option<FormatoDensidad>,
+    rejilla: (usize, usize),
+    ventana_densidad: usize,
+    grabar: Option<FormatoGrabacion>,
+    cada: u32,
+    escala: f32,
+    svg: Option<String>,
+    fase: Option<String>,
+    serial: bool,
+    tui: bool,
+    rendimiento: Option<usize>,
+    respuesta_funcional: bool,
+    presas: Vec<usize>,
+    depredadores: usize,
+    especie: EspecieDepredador,
+    ajustar: Option<String>,
+    modelos: Vec<ModeloPoblacion>,
+    edo: bool,
+    comparar: Option<String>,
+    analizar: Option<String>,
+    salida: Option<String>,
+}
+
+impl Opciones {
+    fn desde_args(args: &[String]) -> Result<Opciones, String> {
+        let mut o = Opciones {
+            dias: None,
+            semilla: ::rand::random(),
+            formato: FORMATO_REPORTES,
+            piramide: PIRAMIDE_EDADES,
+            densidad: None,
+            rejilla: (COLUMNAS_DENSIDAD, FILAS_DENSIDAD),
+            ventana_densidad: DIAS_DENSIDAD,
+            grabar: None,
+            cada: CUADROS_POR_CAPTURA,
+            escala: ESCALA_GRABACION,
+            svg: None,
+            fase: None,
+            serial: false,
+            tui: false,
+            rendimiento: None,
+            respuesta_funcional: false,
+            presas: PRESAS_RESPUESTA_FUNCIONAL.to_vec(),
+            depredadores: DEPREDADORES_INICIALES,
+            especie: EspecieDepredador::Zorro,
+            ajustar: None,
+            modelos: ModeloPoblacion::TODOS.to_vec(),
+            edo: false,
+            comparar: None,
+            analizar: None,
+            salida: None,
+        };
+        let mut i = 0;
+        while i < args.len() {
+            let valor = |i: usize| args.get(i + 1).ok_or(format!("falta el valor de {}", args[i]));
+            let numero = |i: usize| valor(i)?.parse::<u64>().map_err(|e| format!("{} {}: {}", args[i], args[i + 1], e));
+            match args[i].as_str() {
+                "--dias" => { o.dias = Some(numero(i)? as u32); i += 1; }
+                "--semilla" => { o.semilla = numero(i)?; i += 1; }
+                "--formato" => {
+                    let nombre = valor(i)?;
+                    o.formato = FormatoReportes::desde_nombre(nombre).ok_or(format!("formato desconocido '{}'", nombre))?;
+                    i += 1;
+                }
+                "--piramide" => o.piramide = true,
+                "--densidad" => {
+                    let nombre = valor(i)?;
+                    o.densidad = Some(FormatoDensidad::desde_nombre(nombre).ok_or(format!("formato de densidad desconocido '{}'", nombre))?);
+                    i += 1;
+                }
+                "--rejilla" => {
+                    let texto = valor(i)?;
+                    let partes: Vec<usize> = texto.split('x')
+                        .map(|s| s.trim().parse::<usize>().map_err(|e| format!("--rejilla '{}': {}", texto, e)))
+                        .collect::<Result<Vec<usize>, String>>()?;
+                    let [columnas, filas] = partes[..] else { return Err(format!("--rejilla '{}': se espera CxF", texto)) };
+                    o.rejilla = (columnas, filas);
+                    i += 1;
+                }
+                "--grabar" => {
+                    let nombre = valor(i)?;
+                    o.grabar = Some(FormatoGrabacion::desde_nombre(nombre).ok_or(format!("formato de grabacion desconocido '{}'", nombre))?);
+                    i += 1;
+                }
+                "--cada" => { o.cada = numero(i)? as u32; i += 1; }
+                "--escala" => {
+                    o.escala = valor(i)?.parse::<f32>().map_err(|e| format!("--escala {}: {}", args[i + 1], e))?;
+                    if o.escala <= 0.0 {
+                        return Err("--escala debe ser positiva".to_string());
+                    }
+                    i += 1;
+                }
+                "--serial" => o.serial = true,
+                "--tui" => o.tui = true,
+                "--rendimiento" => { o.rendimiento = Some(numero(i)? as usize); i += 1; }
+                "--svg" => { o.svg = Some(valor(i)?.clone()); i += 1; }
+                "--fase" => { o.fase = Some(valor(i)?.clone()); i += 1; }
+                "--ventana-densidad" => { o.ventana_densidad = numero(i)? as usize; i += 1; }
+                "--depredadores" => { o.depredadores = numero(i)? as usize; i += 1; }
+                "--respuesta-funcional" => o.respuesta_funcional = true,
+                "--presas" => {
+                    o.presas = valor(i)?.split(',')
+                        .map(|s| s.trim().parse::<usize>().map_err(|e| format!("--presas '{}': {}", s, e)))
+                        .collect::<Result<Vec<usize>, String>>()?;
+                    i += 1;
+                }
+                "--especie" => {
+                    let nombre = valor(i)?;
+                    o.especie = EspecieDepredador::desde_nombre(nombre).ok_or(format!("especie de depredador desconocida '{}'", nombre))?;
+                    i += 1;
+                }
+                "--edo" => o.edo = true,
+                "--comparar" => { o.comparar = Some(valor(i)?.clone()); i += 1; }
+                "--analizar" => { o.analizar = Some(valor(i)?.clone()); i += 1; }
+                "--salida" => { o.salida = Some(valor(i)?.clone()); i += 1; }
+                "--ajustar" => { o.ajustar = Some(valor(i)?.clone()); i += 1; }
+                "--modelo" => {
+                    let nombre = valor(i)?;
+                    o.modelos = vec![ModeloPoblacion::desde_nombre(nombre).ok_or(format!("modelo desconocido '{}'", nombre))?];
+                    i += 1;
+                }
+                otro => return Err(format!("opcion desconocida '{}'\n{}", otro, AYUDA)),
+            }
+            i += 1;
+        }
+        Ok(o)
+    }
+}
+
+// ==================== EJECUCION SIN INTERFAZ ====================
+pub fn ejecutar(args: &[String]) -> Result<(), String> {
+    if args.iter().any(|a| a == "--ayuda" || a == "-h" || a == "--help") {
+        println!("{}", AYUDA);
+        return Ok(());
+    }
+    let o = Opciones::desde_args(args)?;
+    if let Some(ruta) = &o.analizar {
+        analizar(ruta, o.salida.as_deref().unwrap_or("resumen.json"))
+    } else if let Some(ruta) = &o.ajustar {
+        ajustar(ruta, &o.modelos)
+    } else if o.edo {
+        campo_medio(&o)
+    } else if o.respuesta_funcional {
+        respuesta_funcional(&o)
+    } else if o.tui {
+        tui(&o)
+    } else if let Some(presas) = o.rendimiento {
+        rendimiento(presas, o.semilla)
+    } else {
+        simular(&o)
+    }
+}
+
+// Corre la simulacion completa sin ventana y guarda lo mismo que Esc
+fn simular(o: &Opciones) -> Result<(), String> {
+    let dias = o.dias.unwrap_or(DIAS_SIN_INTERFAZ);
+    let mut sim = Simulacion::new(o.semilla);
+    sim.registrar_piramide = o.piramide;
+    sim.paralelo = !o.serial;
+    sim.densidad = MapaDensidad::new(o.rejilla.0, o.rejilla.1, o.ventana_densidad);
+    sim.abrir_salida(o.formato)?;
+    println!("Simulando {} dias (semilla {}, configuracion {})", dias, sim.semilla, sim.metadatos.hash_configuracion);
+    let mut grabadora = o.grabar.map(|f| Grabadora::new(f, o.cada));
+    let mut eventos_mostrados = 0;
+    while sim.dias < dias {
+        if sim.paso(PASO_SIN_INTERFAZ) {
+            for e in &sim.planificador.registro()[eventos_mostrados..] {
+                println!("Día {}: {} ({}) - {} afectados, {}", e.dia, e.evento, e.origen, e.afectados, e.detalle);
+            }
+            eventos_mostrados = sim.planificador.registro().len();
+            if let Some(formato) = o.densidad {
+                sim.densidad.exportar(RUTA_DENSIDAD, sim.dias, formato).map_err(|e| format!("{}: {}", RUTA_DENSIDAD, e))?;
+            }
+        }
+        if let Some(g) = grabadora.as_mut()
+            && g.toca_capturar()
+        {
+            g.agregar(&rasterizar_mundo(&sim.terreno, &sim.mundo, sim.tiempo_acumulado, o.escala))?;
+        }
+    }
+    if let Some(g) = grabadora.as_mut() {
+        g.finalizar();
+        println!("Grabacion: {} cuadros en {}", g.guardados(), g.formato().ruta());
+    }
+    if let Some(ruta) = &o.svg {
+        let mut svg = RendererSvg::new();
+        dibujar_mundo(&mut svg, &sim.terreno, &sim.mundo, sim.tiempo_acumulado);
+        svg.guardar(ruta).map_err(|e| format!("{}: {}", ruta, e))?;
+        println!("Mundo guardado en {}", ruta);
+    }
+    if let Some(ruta) = &o.fase {
+        guardar_plano_fase_svg(&sim.reportes, EjePresas::Total, EjeDepredadores::Conteo, ruta).map_err(|e| format!("{}: {}", ruta, e))?;
+        println!("Plano de fase guardado en {}", ruta);
+    }
+    sim.cerrar_salida();
+    if let Some(r) = sim.reportes.last() {
+        println!("Dia {}: {} presas, {} depredadores vivos", r.dia, r.conteo_total, r.depredadores_vivos);
+    }
+    println!("Reportes guardados en {}", o.formato.rutas().join(", "));
+    if o.piramide {
+        println!("Piramide de edades en {}", o.formato.ruta_piramide());
+    }
+    if let Some(formato) = o.densidad {
+        println!("Mapas de densidad en {}/<capa>_dia_<n>.{}", RUTA_DENSIDAD, formato.extension());
+    }
+    Ok(())
+}
+
+// La simulacion en la terminal hasta que se sale; los reportes se escriben como sin interfaz
+fn tui(o: &Opciones) -> Result<(), String> {
+    let mut sim = Simulacion::new(o.semilla);
+    sim.registrar_piramide = o.piramide;
+    sim.paralelo = !o.serial;
+    sim.abrir_salida(o.formato)?;
+    terminal::ejecutar(&mut sim)?;
+    sim.cerrar_salida();
+    println!("Dia {} (semilla {}). Reportes guardados en {}", sim.dias, sim.semilla, o.formato.rutas().join(", "));
+    Ok(())
+}
+
+fn rendimiento(presas: usize, semilla: u64) -> Result<(), String> {
+    println!("Rendimiento del paso: {} presas, {} pasos (semilla {})", presas, PASOS_RENDIMIENTO, semilla);
+    for m in medir_rendimiento(presas, PASOS_RENDIMIENTO, semilla)? {
+        let nombre = if m.hilos == 0 { "serial".to_string() } else { format!("{} hilos", m.hilos) };
+        println!("  {:>8}: {:8.3} ms/paso, aceleracion {:.2}x", nombre, m.ms_por_paso, m.aceleracion);
+    }
+    Ok(())
+}
+
+fn respuesta_funcional(o: &Opciones) -> Result<(), String> {
+    let dias = o.dias.unwrap_or(DIAS_RESPUESTA_FUNCIONAL);
+    println!("Respuesta funcional de {} ({}), {} dias por densidad (semilla {})",
+             o.especie.nombre(), o.especie.estrategia().nombre(), dias, o.semilla);
+    let puntos = medir_respuesta_funcional(&o.presas, dias, o.depredadores, o.especie, o.semilla);
+    for p in &puntos {
+        println!("  {:>4} presas (densidad {:.2}): {:.2} capturas/depredador/dia, {:.2} ataques fallidos",
+                 p.presas, p.densidad, p.capturas_por_depredador_dia, p.ataques_fallidos_por_depredador_dia);
+    }
+
+    let ajustes: Vec<_> = ModeloHolling::TODOS.iter().map(|&m| ajustar_holling(m, &puntos)).collect();
+    for a in &ajustes {
+        println!("  {}: a = {:.4}, h = {:.4} dias, SCE = {:.3}, R² = {:.3}, AIC = {:.2}",
+                 a.modelo, a.tasa_ataque, a.tiempo_manipulacion, a.suma_cuadrados, a.r_cuadrado, a.aic);
+    }
+    if let Some(mejor) = mejor_ajuste(&ajustes) {
+        println!("  Mejor modelo (AIC): {}", mejor.modelo);
+    }
+    // Referencia: la espera tras comer es el minimo de manipulacion por captura
+    println!("  Tiempo de manipulacion minimo (TIEMPO_ESPERA_COMIDA): {:.4} dias", TIEMPO_ESPERA_COMIDA / DURACION_DIA);
+
+    guardar_respuesta_csv(&puntos, "respuesta_funcional.csv").map_err(|e| e.to_string())?;
+    guardar_ajustes_csv(&ajustes, "ajuste_holling.csv").map_err(|e| e.to_string())?;
+    println!("Resultados guardados en respuesta_funcional.csv y ajuste_holling.csv");
+    Ok(())
+}
+
+// Ajuste de modelos agregados a una serie de reportes (simulada o de campo)
+fn ajustar(ruta: &str, modelos: &[ModeloPoblacion]) -> Result<(), String> {
+    let reportes = cargar_reportes_csv(ruta).map_err(|e| format!("{}: {}", ruta, e))?;
+    println!("{} dias leidos de {}", reportes.len(), ruta);
+    for &modelo in modelos {
+        let ajuste = ajustar_poblacion(modelo, &reportes)?;
+        println!("{}:", modelo.nombre());
+        for (nombre, valor) in modelo.nombres_parametros().iter().zip(&ajuste.parametros) {
+            println!("  {} = {:.5}", nombre, valor);
+        }
+        println!("  R² presas = {:.3}, R² depredadores = {:.3}, AIC = {:.2}",
+                 ajuste.r_cuadrado_presas, ajuste.r_cuadrado_depredadores, ajuste.aic);
+        match ajuste.equilibrio {
+            Some((n, d)) => println!("  Equilibrio: {:.1} presas, {:.2} depredadores", n, d),
+            None => println!("  Sin equilibrio de coexistencia positivo"),
+        }
+        if !ajuste.avisos.is_empty() {
+            println!("  AJUSTE DEGENERADO, los parametros no son interpretables:");
+            for aviso in &ajuste.avisos {
+                println!("    - {}", aviso);
+            }
+        }
+        let curvas = format!("ajuste_{}.csv", modelo.abreviatura());
+        let parametros = format!("parametros_{}.csv", modelo.abreviatura());
+        guardar_ajuste_csv(&ajuste, &curvas).map_err(|e| e.to_string())?;
+        guardar_parametros_csv(&ajuste, &parametros).map_err(|e| e.to_string())?;
+        println!("  Guardado en {} y {}", curvas, parametros);
+    }
+    Ok(())
+}
+
+// Modelo determinista con la misma configuracion; opcionalmente junto a una corrida del modelo de agentes
+fn campo_medio(o: &Opciones) -> Result<(), String> {
+    let abm = match &o.comparar {
+        Some(ruta) => Some(cargar_reportes_csv(ruta).map_err(|e| format!("{}: {}", ruta, e))?),
+        None => None,
+    };
+    let dias = o.dias.or(abm.as_ref().and_then(|r| r.last().map(|r| r.dia))).unwrap_or(DIAS_SIN_INTERFAZ);
+    let edo = simular_campo_medio(dias);
+    guardar_reportes_csv(&edo, "reportes_edo.csv").map_err(|e| e.to_string())?;
+    if let Some(r) = edo.last() {
+        println!("Campo medio, dia {}: {} presas, {} depredadores", r.dia, r.conteo_total, r.depredadores_vivos);
+    }
+    println!("Serie guardada en reportes_edo.csv");
+
+    if let Some(abm) = abm {
+        guardar_comparacion_csv(&abm, &edo, "comparacion_edo.csv").map_err(|e| e.to_string())?;
+        println!("Comparacion guardada en comparacion_edo.csv");
+    }
+    Ok(())
+}
+
+fn analizar(ruta: &str, salida: &str) -> Result<(), String> {
+    let reportes = cargar_reportes_csv(ruta).map_err(|e| format!("{}: {}", ruta, e))?;
+    let resumen = analizar_reportes(&reportes)?;
+    for s in &resumen.series {
+        println!("  {:<13} media {:>7.2}  CV {:>5}  min {:>5.0} (dia {})  amplitud {:>6.1}  periodo {}",
+                 s.nombre, s.media,
+                 s.coeficiente_variacion.map_or("-".to_string(), |c| format!("{:.2}", c)),
+                 s.minimo, s.dia_minimo, s.amplitud,
+                 s.periodo_dias.map_or("-".to_string(), |p| format!("{} dias", p)));
+    }
+    if let Some(e) = &resumen.primera_extincion {
+        println!("  Primera extincion: {} el dia {}", e.serie, e.dia);
+    }
+    if let (Some(k), Some(c)) = (resumen.desfase_presas_depredadores, resumen.correlacion_en_desfase) {
+        println!("  Desfase presas -> depredadores: {} dias (correlacion {:.2})", k, c);
+    }
+    guardar_resumen_json(&resumen, salida).map_err(|e| e.to_string())?;
+    println!("Resumen guardado en {}", salida);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::entidades::componentes::{Posicion, Velocidad};
+use crate::utilidades::configuraciones::*;
+
+/// Desplazamiento (dx, dy) de a hacia b; en modo toroidal toma el camino mas corto
+pub fn desplazamiento(ax: f32, ay: f32, bx: f32, by: f32) -> (f32, f32) {
+    desplazamiento_en(MODO_BORDE, ax, ay, bx, by)
+}
+
+pub fn desplazamiento_en(modo: ModoBorde, ax: f32, ay: f32, bx: f32, by: f32) -> (f32, f32) {
+    let mut dx = bx - ax;
+    let mut dy = by - ay;
+    if modo == ModoBorde::Toroidal {
+        if dx.abs() > ANCHO_MUNDO / 2.0 { dx -= ANCHO_MUNDO * dx.signum(); }
+        if dy.abs() > ALTO_MUNDO / 2.0 { dy -= ALTO_MUNDO * dy.signum(); }
+    }
+    (dx, dy)
+}
+
+pub fn distancia(ax: f32, ay: f32, bx: f32, by: f32) -> f32 {
+    let (dx, dy) = desplazamiento(ax, ay, bx, by);
+    (dx * dx + dy * dy).sqrt()
+}
+
+// Aplica el modo de borde configurado; devuelve true si salio del mundo (emigro), y entonces
+// quien llama lo retira
+pub fn aplicar_borde(pos: &mut Posicion, vel: &mut Velocidad) -> bool {
+    aplicar_borde_en(MODO_BORDE, pos, vel)
+}
+
+pub fn aplicar_borde_en(modo: ModoBorde, pos: &mut Posicion, vel: &mut Velocidad) -> bool {
+    let Posicion { x, y } = *pos;
+    match modo {
+        ModoBorde::Reflectivo => {
+            if x < 0.0 { vel.vx = vel.vx.abs(); }
+            if x > ANCHO_MUNDO { vel.vx = -vel.vx.abs(); }
+            if y < 0.0 { vel.vy = vel.vy.abs(); }
+            if y > ALTO_MUNDO { vel.vy = -vel.vy.abs(); }
+            *pos = Posicion { x: x.clamp(0.0, ANCHO_MUNDO), y: y.clamp(0.0, ALTO_MUNDO) };
+            false
+        }
+        ModoBorde::Toroidal => {
+            *pos = Posicion { x: x.rem_euclid(ANCHO_MUNDO), y: y.rem_euclid(ALTO_MUNDO) };
+            false
+        }
+        ModoBorde::Absorbente => !(0.0..=ANCHO_MUNDO).contains(&x) || !(0.0..=ALTO_MUNDO).contains(&y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn en(x: f32, y: f32, vx: f32, vy: f32) -> (Posicion, Velocidad) {
+        (Posicion { x, y }, Velocidad { vx, vy })
+    }
+
+    #[test]
+    fn desplazamiento_toroidal_toma_el_camino_corto() {
+        // Cerca de esquinas opuestas: directo cruza el mundo, toroidal pasa por el borde
+        assert_eq!(desplazamiento_en(ModoBorde::Reflectivo, 10.0, 10.0, ANCHO_MUNDO - 10.0, ALTO_MUNDO - 10.0),
+                   (ANCHO_MUNDO - 20.0, ALTO_MUNDO - 20.0));
+        assert_eq!(desplazamiento_en(ModoBorde::Toroidal, 10.0, 10.0, ANCHO_MUNDO - 10.0, ALTO_MUNDO - 10.0), (-20.0, -20.0));
+        assert_eq!(desplazamiento_en(ModoBorde::Toroidal, ANCHO_MUNDO - 10.0, 50.0, 10.0, 50.0), (20.0, 0.0));
+        // Sin cruzar la mitad del mundo no cambia
+        assert_eq!(desplazamiento_en(ModoBorde::Toroidal, 100.0, 100.0, 300.0, 50.0), (200.0, -50.0));
+    }
+
+    #[test]
+    fn reflectivo_devuelve_al_mundo_y_da_vuelta_la_velocidad() {
+        let (mut pos, mut vel) = en(ANCHO_MUNDO + 3.0, -2.0, 1.5, -0.5);
+        assert!(!aplicar_borde_en(ModoBorde::Reflectivo, &mut pos, &mut vel));
+        assert_eq!((pos, vel), en(ANCHO_MUNDO, 0.0, -1.5, 0.5));
+    }
+
+    #[test]
+    fn toroidal_sale_por_un_lado_y_entra_por_el_otro() {
+        let (mut pos, mut vel) = en(-5.0, ALTO_MUNDO + 5.0, -1.0, 1.0);
+        assert!(!aplicar_borde_en(ModoBorde::Toroidal, &mut pos, &mut vel));
+        assert_eq!((pos, vel), en(ANCHO_MUNDO - 5.0, 5.0, -1.0, 1.0));
+    }
+
+    #[test]
+    fn absorbente_retira_solo_a_los_que_salen() {
+        let (mut pos, mut vel) = en(ANCHO_MUNDO, 0.0, 1.0, 1.0);
+        assert!(!aplicar_borde_en(ModoBorde::Absorbente, &mut pos, &mut vel));
+        let (mut pos, mut vel) = en(ANCHO_MUNDO + 0.1, 10.0, 1.0, 0.0);
+        assert!(aplicar_borde_en(ModoBorde::Absorbente, &mut pos, &mut vel));
+        // No la mueve: la retira quien llama
+        assert_eq!((pos, vel), en(ANCHO_MUNDO + 0.1, 10.0, 1.0, 0.0));
+    }
+}
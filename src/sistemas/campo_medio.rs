@@ -0,0 +1,217 @@
+use crate::entidades::{Especie, EspecieDepredador};
+use crate::sistemas::estaciones::{Estacion, en_ventana, factor_enfermedad, factor_consumo_depredador};
+use crate::utilidades::configuraciones::*;
+use crate::utilidades::csv::EstadisticasDiarias;
+use crate::utilidades::edo::paso_rk4;
+
+const ESPECIES: [Especie; 3] = [Especie::Conejo, Especie::Raton, Especie::Ardilla];
+const SUB_PASOS_DIA: usize = 20;
+
+// Indices del estado: presas por especie, depredadores, reserva media y muertes acumuladas
+const DEPREDADORES: usize = 3;
+const RESERVA: usize = 4;
+const MUERTES_PREDACION: usize = 5;
+const MUERTES_ENFERMEDAD: usize = 6;
+
+// ==================== MODELO DE CAMPO MEDIO ====================
+// Version determinista de la misma ecologia con las constantes de configuraciones.rs:
+// crecimiento logistico hasta poblacion_maxima dentro de la ventana de reproduccion,
+// mortalidad por enfermedad, respuesta funcional tipo II y hambre de los depredadores.
+// Las tasas que en el modelo de agentes salen de encuentros se aproximan por mezcla homogenea.
+struct TasasEspecie {
+    crecimiento: f32, // crias por adulto y dia dentro de la ventana
+    capacidad: f32,
+    captura: f32,     // fraccion de los encuentros que terminan en captura
+    peso: f32,
+}
+
+impl TasasEspecie {
+    fn de(especie: Especie) -> Self {
+        let probs: &[f32] = match especie {
+            Especie::Conejo => &PROB_CRIAS_CONEJO,
+            Especie::Raton => &PROB_CRIAS_RATON,
+            Especie::Ardilla => &PROB_CRIAS_ARDILLA,
+        };
+        let media_crias: f32 = probs.iter().enumerate().map(|(k, p)| k as f32 * p).sum();
+        // Una camada por pareja cada tiempo de maduracion
+        let crecimiento = media_crias / (2.0 * especie.edad_reproduccion() as f32);
+        // Solapamiento medio de actividad entre dia y noche (la simulacion empieza con zorros)
+        let depredador = EspecieDepredador::Zorro.actividad();
+        let solapamiento = FRACCION_DIURNA * depredador.0 * especie.actividad().0
+            + (1.0 - FRACCION_DIURNA) * depredador.1 * especie.actividad().1;
+        Self {
+            crecimiento,
+            capacidad: especie.poblacion_maxima() as f32,
+            captura: solapamiento * PROB_CAPTURA_BASE,
+            peso: especie.peso_gompertz(especie.edad_reproduccion()),
+        }
+    }
+}
+
+pub struct CampoMedio {
+    estado: Vec<f64>,
+    dia: u32,
+    tasas: Vec<TasasEspecie>,
+}
+
+impl Default for CampoMedio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CampoMedio {
+    /// Mismas poblaciones iniciales que el modelo de agentes (presas repartidas entre especies)
+    pub fn new() -> Self {
+        let por_especie = PRESAS_INICIALES as f64 / ESPECIES.len() as f64;
+        Self {
+            estado: vec![por_especie, por_especie, por_especie, DEPREDADORES_INICIALES as f64, 0.0, 0.0, 0.0],
+            dia: 0,
+            tasas: ESPECIES.iter().map(|&e| TasasEspecie::de(e)).collect(),
+        }
+    }
+
+    /// Area barrida por un depredador en un dia como fraccion del mundo
+    pub fn tasa_busqueda() -> f32 {
+        let cuadros_por_dia = DURACION_DIA / PASO_SIN_INTERFAZ;
+        2.0 * (RADIO_DEPREDADOR + RADIO_PRESA) * VEL_MAX_DEPREDADOR * cuadros_por_dia / (ANCHO_MUNDO * ALTO_MUNDO)
+    }
+
+    // Derivadas con el forzamiento estacional del dia fijo (los procesos diarios del ABM son por dia)
+    fn derivada(&self, y: &[f64]) -> Vec<f64> {
+        let dia = self.dia;
+        let a = Self::tasa_busqueda() as f64;
+        let h = (TIEMPO_ESPERA_COMIDA / DURACION_DIA) as f64;
+        let presas: Vec<f64> = y[..3].iter().map(|n| n.max(0.0)).collect();
+        let (d, reserva) = (y[DEPREDADORES].max(0.0), y[RESERVA]);
+
+        // Respuesta tipo II con varias presas: la manipulacion es comun a todas
+        let encuentros: Vec<f64> = presas.iter().zip(&self.tasas).map(|(n, t)| a * t.captura as f64 * n).collect();
+        let denominador = 1.0 + h * encuentros.iter().sum::<f64>();
+
+        let prob_muerte_enferma = (1.0 - PROB_RECUPERACION_DIARIA_PRESA).powi(MAX_DIAS_SIN_RECUPERAR_PRESA as i32);
+        let mortalidad_enfermedad = (PROB_ENFERMAR_DIARIA_PRESA * factor_enfermedad(dia) * prob_muerte_enferma) as f64;
+
+        let mut dy = vec![0.0; y.len()];
+        let mut ingesta = 0.0;
+        for (i, (&especie, t)) in ESPECIES.iter().zip(&self.tasas).enumerate() {
+            let n = presas[i];
+            let capturas = encuentros[i] / denominador * d;
+            let nacimientos = if en_ventana(dia, especie.ventana_reproduccion()) {
+                t.crecimiento as f64 * n * (1.0 - n / t.capacidad as f64)
+            } else {
+                0.0
+            };
+            dy[i] = nacimientos - capturas - mortalidad_enfermedad * n;
+            dy[MUERTES_PREDACION] += capturas;
+            dy[MUERTES_ENFERMEDAD] += mortalidad_enfermedad * n;
+            ingesta += encuentros[i] / denominador * t.peso as f64;
+        }
+
+        // Reserva media por depredador: ingesta menos consumo, persecucion continua y ataques
+        let gasto = (CONSUMO_DIARIO_DEPREDADOR * factor_consumo_depredador(dia)) as f64
+            + (COSTO_PERSECUCION * DURACION_DIA) as f64
+            + COSTO_ATAQUE as f64 * encuentros.iter().sum::<f64>() / denominador;
+        dy[RESERVA] = if reserva <= 0.0 && ingesta < gasto { 0.0 } else { ingesta - gasto };
+
+        // Los depredadores no se reproducen; por debajo del umbral minimo mueren tras MAX_DIAS
+        if dia > DIAS_INMUNIDAD && reserva < UMBRAL_MINIMO_DEPREDADOR as f64 {
+            dy[DEPREDADORES] = -d / MAX_DIAS_SIN_RECUPERAR_DEPREDADOR as f64;
+        }
+        dy
+    }
+
+    /// Avanza un dia y devuelve el reporte con el mismo esquema que el modelo de agentes
+    pub fn avanzar_dia(&mut self) -> EstadisticasDiarias {
+        self.dia += 1;
+        let antes = self.estado.clone();
+        let dt = 1.0 / SUB_PASOS_DIA as f64;
+        for _ in 0..SUB_PASOS_DIA {
+            self.estado = paso_rk4(|y| self.derivada(y), &self.estado, dt);
+        }
+        self.estado[RESERVA] = self.estado[RESERVA].max(0.0);
+        for n in self.estado[..=DEPREDADORES].iter_mut() {
+            *n = n.max(0.0);
+        }
+
+        let conteo = |i: usize| self.estado[i].round() as usize;
+        // Diferencia de acumulados redondeados para que la suma de los dias no pierda las fracciones
+        let diario = |i: usize| (self.estado[i].round() - antes[i].round()).max(0.0) as u32;
+        let depredadores_vivos = conteo(DEPREDADORES);
+        EstadisticasDiarias {
+            dia: self.dia,
+            estacion: Estacion::del_dia(self.dia).nombre().to_string(),
+            conteo_conejos: conteo(0),
+            conteo_ratones: conteo(1),
+            conteo_ardillas: conteo(2),
+            conteo_total: conteo(0) + conteo(1) + conteo(2),
+            muertes_por_predacion: diario(MUERTES_PREDACION),
+            muertes_por_enfermedad: diario(MUERTES_ENFERMEDAD),
+            depredadores_enfermos: if self.estado[RESERVA] < UMBRAL_MINIMO_DEPREDADOR as f64 { depredadores_vivos } else { 0 },
+            depredadores_vivos,
+            reserva_total_depredadores: (self.estado[RESERVA] * self.estado[DEPREDADORES]) as f32,
+            ..Default::default()
+        }
+    }
+}
+
+/// Serie diaria del modelo de campo medio
+pub fn simular_campo_medio(dias: u32) -> Vec<EstadisticasDiarias> {
+    let mut modelo = CampoMedio::new();
+    (0..dias).map(|_| modelo.avanzar_dia()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sistemas::Simulacion;
+
+    // Sin depredadores solo quedan el crecimiento logistico y la enfermedad: cada especie llega
+    // a poblacion_maxima en su ventana de reproduccion (la enfermedad la baja un poco fuera de ella)
+    // y nunca la supera
+    #[test]
+    fn sin_depredadores_las_presas_llegan_a_la_capacidad() {
+        let mut modelo = CampoMedio::new();
+        modelo.estado[DEPREDADORES] = 0.0;
+        let reportes: Vec<_> = (0..2 * DURACION_ANIO).map(|_| modelo.avanzar_dia()).collect();
+        for (i, especie) in ESPECIES.iter().enumerate() {
+            let conteos: Vec<usize> = reportes[DURACION_ANIO as usize..].iter()
+                .map(|r| [r.conteo_conejos, r.conteo_ratones, r.conteo_ardillas][i])
+                .collect();
+            let maximo = *conteos.iter().max().unwrap();
+            assert!(maximo <= especie.poblacion_maxima() && maximo + 1 >= especie.poblacion_maxima(),
+                    "{:?}: maximo {} frente a {}", especie, maximo, especie.poblacion_maxima());
+        }
+        assert!(reportes.iter().all(|r| r.muertes_por_predacion == 0 && r.depredadores_vivos == 0));
+    }
+
+    #[test]
+    fn muertes_diarias_suman_el_acumulado() {
+        let mut modelo = CampoMedio::new();
+        let reportes: Vec<_> = (0..200).map(|_| modelo.avanzar_dia()).collect();
+        let suma = |f: fn(&EstadisticasDiarias) -> u32| reportes.iter().map(f).sum::<u32>() as f64;
+        assert!(suma(|r| r.muertes_por_predacion) > 0.0);
+        assert_eq!(suma(|r| r.muertes_por_predacion), modelo.estado[MUERTES_PREDACION].round());
+        assert_eq!(suma(|r| r.muertes_por_enfermedad), modelo.estado[MUERTES_ENFERMEDAD].round());
+    }
+
+    // Un reporte por dia, con los mismos dias y estaciones que el modelo de agentes
+    #[test]
+    fn serie_con_el_esquema_del_modelo_de_agentes() {
+        let serie = simular_campo_medio(120);
+        assert_eq!(serie.len(), 120);
+        for (i, r) in serie.iter().enumerate() {
+            assert_eq!(r.dia, i as u32 + 1);
+            assert_eq!(r.estacion, Estacion::del_dia(r.dia).nombre());
+        }
+
+        let mut sim = Simulacion::new(5);
+        while sim.dias < 3 {
+            sim.paso(PASO_SIN_INTERFAZ);
+        }
+        let agentes: Vec<_> = sim.reportes.iter().map(|r| (r.dia, r.estacion.clone())).collect();
+        let campo: Vec<_> = serie[..agentes.len()].iter().map(|r| (r.dia, r.estacion.clone())).collect();
+        assert_eq!(agentes.len(), 3);
+        assert_eq!(agentes, campo);
+    }
+}
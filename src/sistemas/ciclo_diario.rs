@@ -0,0 +1,42 @@
+use std::f32::consts::PI;
+use crate::utilidades::configuraciones::*;
+
+// ==================== DIA Y NOCHE ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Periodo { Dia, Noche }
+
+impl Periodo {
+    // Cada dia simulado empieza con el amanecer; la noche ocupa el resto de DURACION_DIA
+    pub fn actual(tiempo_del_dia: f32) -> Periodo {
+        if tiempo_del_dia / DURACION_DIA < FRACCION_DIURNA {
+            Periodo::Dia
+        } else {
+            Periodo::Noche
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Periodo::Dia => "Día",
+            Periodo::Noche => "Noche",
+        }
+    }
+}
+
+/// Nivel de oscuridad en [0, 1]: 0 de dia, sube al anochecer y es maximo a medianoche
+pub fn oscuridad(tiempo_del_dia: f32) -> f32 {
+    let f = (tiempo_del_dia / DURACION_DIA).clamp(0.0, 1.0);
+    if f < FRACCION_DIURNA {
+        0.0
+    } else {
+        (PI * (f - FRACCION_DIURNA) / (1.0 - FRACCION_DIURNA)).sin()
+    }
+}
+
+/// Nivel de actividad de un par (dia, noche) en el periodo actual
+pub fn actividad(niveles: (f32, f32), periodo: Periodo) -> f32 {
+    match periodo {
+        Periodo::Dia => niveles.0,
+        Periodo::Noche => niveles.1,
+    }
+}
@@ -1,7 +1,8 @@
-use crate::entidades::Organismo;
+use crate::entidades::Mundo;
+use crate::sistemas::bordes::distancia;
 
-pub fn colision(a: &dyn Organismo, b: &dyn Organismo) -> bool {
-    let dx = a.x() - b.x();
-    let dy = a.y() - b.y();
-    (dx * dx + dy * dy).sqrt() < a.r() + b.r()
-}
\ No newline at end of file
+// Las filas a y b deben tener Posicion y Cuerpo
+pub fn colision(mundo: &Mundo, a: usize, b: usize) -> bool {
+    let (pa, pb) = (mundo.posiciones[a], mundo.posiciones[b]);
+    distancia(pa.x, pa.y, pb.x, pb.y) < mundo.cuerpos[a].radio + mundo.cuerpos[b].radio
+}
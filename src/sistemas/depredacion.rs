@@ -1,19 +1,189 @@
-use crate::entidades::{Presa, Depredador};
+use ::rand::Rng;
+use crate::entidades::{Mundo, Terreno, EstadoSalud};
 use crate::utilidades::configuraciones::*;
 use crate::sistemas::colision::colision;
+use crate::sistemas::movimiento::presa_escondida;
+use crate::sistemas::ciclo_diario::{actividad, Periodo};
+use crate::sistemas::diario::ContadoresDiarios;
 
-pub fn depredadores_comer(depredadores: &mut Vec<Depredador>, presas: &mut Vec<Presa>, muertes_pred: &mut u32) {
-    for d in depredadores.iter_mut() {
-        if d.cooldown() <= 0.0 {
-            for p in presas.iter_mut() {
-                if p.esta_vivo() && p.edad() >= p.especie().edad_sacrificio() && colision(d, p) {
-                    d.set_reserva(d.reserva() + p.peso_actual());
-                    p.matar();
-                    *muertes_pred += 1;
-                    d.set_cooldown(TIEMPO_ESPERA_COMIDA);
-                    break;
+// Probabilidad de que el ataque del cazador de la fila d a la presa de la fila p termine en captura
+pub fn probabilidad_captura(mundo: &Mundo, d: usize, p: usize, terreno: &Terreno) -> f32 {
+    let (pos_d, pos_p) = (mundo.posiciones[d], mundo.posiciones[p]);
+    let celda_p = terreno.celda_en(pos_p.x, pos_p.y);
+    // Ventaja de velocidad: 0.5 con velocidades iguales, tiende a 1 si la presa esta quieta
+    let loc_d = mundo.locomociones[d];
+    let vel_d = loc_d.rapidez * loc_d.factor(terreno.celda_en(pos_d.x, pos_d.y));
+    let v = mundo.velocidades[p];
+    let vel_p = (v.vx * v.vx + v.vy * v.vy).sqrt() * mundo.locomociones[p].factor(celda_p);
+    let ventaja = if vel_d + vel_p > 0.0 { vel_d / (vel_d + vel_p) } else { 0.5 };
+
+    let factor_presa = if mundo.salud[p].estado == EstadoSalud::Enfermo { FACTOR_CAPTURA_PRESA_ENFERMA } else { 1.0 };
+    let factor_depredador = if mundo.salud[d].estado == EstadoSalud::Enfermo { FACTOR_CAPTURA_DEPREDADOR_ENFERMO } else { 1.0 };
+    let factor_peso = (PESO_REFERENCIA_CAPTURA / mundo.crecimientos[p].peso.max(0.1)).clamp(0.5, 1.5);
+    // En el bosque la presa tiene donde esquivar: vale 1 en campo abierto
+    let factor_cobertura = 1.0 - PESO_COBERTURA_CAPTURA * (1.0 - celda_p.visibilidad());
+
+    (PROB_CAPTURA_BASE * 2.0 * ventaja * factor_presa * factor_depredador * factor_peso * factor_cobertura).clamp(0.0, 1.0)
+}
+
+// Persecucion: el tiempo con objetivo cuesta reserva de la Dieta, proporcional a dt
+pub fn gasto_persecucion(mundo: &mut Mundo, dt: f32) {
+    for (i, _) in mundo.cazas.iter().filter(|(_, c)| c.objetivo.is_some()) {
+        if let Some(dieta) = mundo.dietas.get_mut(i) {
+            dieta.reserva = (dieta.reserva - COSTO_PERSECUCION * dt).max(0.0);
+        }
+    }
+}
+
+// Cruza las filas con Caza (vivas y sin espera) con las presas, las filas con Especie.
+// El encuentro solo prospera con probabilidad igual al solapamiento de actividad de ambos, y se
+// decide una vez por contacto: mientras la presa siga tocando al cazador no se vuelve a tirar.
+// Luego el ataque cuesta reserva y tiene exito con probabilidad_captura, si falla hay espera
+// Las capturas se cuentan tambien por estrategia de caza (indice de EstrategiaCaza::TODAS).
+// Los cazadores muertos no cazan (solo se retiran del mundo con bordes absorbentes)
+pub fn depredadores_comer(mundo: &mut Mundo, terreno: &Terreno, periodo: Periodo, rng: &mut impl Rng,
+                          contadores: &mut ContadoresDiarios) {
+    let cazadores: Vec<usize> = mundo.cazas.filas().filter(|&d| mundo.esta_vivo(d)).collect();
+    let presas: Vec<usize> = mundo.especies.filas().collect();
+    for d in cazadores {
+        if mundo.esperas[d].restante > 0.0 {
+            continue;
+        }
+        let actividad_d = actividad(mundo.locomociones[d].actividad, periodo);
+        let mut contactos = Vec::new();
+        let mut ataco = false;
+        for &p in &presas {
+            let especie = mundo.especies[p];
+            if !mundo.esta_vivo(p) || mundo.crecimientos[p].edad < especie.edad_sacrificio()
+                || presa_escondida(mundo.posiciones[p], terreno) || !colision(mundo, d, p) {
+                continue;
+            }
+            let id = mundo.id(p);
+            let nuevo = !mundo.cazas[d].contactos.contains(&id);
+            contactos.push(id);
+            if !ataco && nuevo && rng.gen_range(0.0..1.0) < actividad_d * actividad(mundo.locomociones[p].actividad, periodo) {
+                ataco = true;
+                let dieta = &mut mundo.dietas[d];
+                dieta.reserva = (dieta.reserva - COSTO_ATAQUE).max(0.0);
+                if rng.gen_range(0.0..1.0) < probabilidad_captura(mundo, d, p, terreno) {
+                    let peso = mundo.crecimientos[p].peso;
+                    mundo.dietas[d].reserva += peso;
+                    if let Some(bitacora) = mundo.bitacoras.get_mut(d) {
+                        bitacora.registrar_captura(especie, peso);
+                    }
+                    mundo.matar(p);
+                    contadores.muertes_por_predacion += 1;
+                    contadores.capturas_por_estrategia[mundo.cazas[d].estrategia.indice()] += 1;
+                    mundo.esperas[d].restante = TIEMPO_ESPERA_COMIDA;
+                } else {
+                    contadores.ataques_fallidos += 1;
+                    mundo.esperas[d].restante = TIEMPO_ESPERA_ATAQUE_FALLIDO;
                 }
             }
         }
+        mundo.cazas[d].contactos = contactos;
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::SeedableRng;
+    use ::rand::rngs::StdRng;
+    use crate::entidades::componentes::{Velocidad, Crecimiento};
+    use crate::entidades::{Especie, EspecieDepredador, plantilla_presa, plantilla_depredador};
+
+    // Presa adulta (fila 0) y depredador (fila 1) en el mismo punto de campo abierto
+    fn encuentro(rng: &mut StdRng) -> Mundo {
+        let mut mundo = Mundo::default();
+        mundo.crear(plantilla_presa(100.0, 100.0, Especie::Conejo, rng));
+        let edad = Especie::Conejo.edad_sacrificio();
+        mundo.crecimientos[0] = Crecimiento { edad, peso: Especie::Conejo.peso_gompertz(edad) };
+        mundo.crear(plantilla_depredador(100.0, 100.0, EspecieDepredador::Zorro, rng));
+        mundo
+    }
+
+    // Intentos de caza (olvidando contactos y esperas) hasta que la presa muere o se agotan
+    fn cazar(mundo: &mut Mundo, rng: &mut StdRng) -> ContadoresDiarios {
+        let terreno = Terreno::abierto();
+        let mut contadores = ContadoresDiarios::default();
+        for i in 0..500 {
+            let periodo = if i % 2 == 0 { Periodo::Dia } else { Periodo::Noche };
+            depredadores_comer(mundo, &terreno, periodo, rng, &mut contadores);
+            if !mundo.esta_vivo(0) {
+                break;
+            }
+            mundo.cazas[1].contactos.clear();
+            mundo.esperas[1].restante = 0.0;
+        }
+        contadores
+    }
+
+    // Campo abierto a la izquierda y bosque a la derecha
+    fn campo_y_bosque() -> Terreno {
+        Terreno::desde_texto(".B").unwrap()
+    }
+
+    // Agrega al mundo (con el depredador en la fila 0) una presa en la fila 1
+    fn con_presa(mut mundo: Mundo, x: f32, velocidad: f32, peso: f32, rng: &mut StdRng) -> Mundo {
+        mundo.crear(plantilla_presa(x, ALTO_MUNDO / 2.0, Especie::Conejo, rng));
+        mundo.velocidades[1] = Velocidad { vx: velocidad, vy: 0.0 };
+        mundo.crecimientos[1].peso = peso;
+        mundo
+    }
+
+    #[test]
+    fn probabilidad_de_captura_segun_velocidad_peso_y_cobertura() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let terreno = campo_y_bosque();
+        let (campo, bosque) = (ANCHO_MUNDO * 0.25, ANCHO_MUNDO * 0.75);
+        let mut solo = Mundo::default();
+        solo.crear(plantilla_depredador(campo, ALTO_MUNDO / 2.0, EspecieDepredador::Zorro, &mut rng));
+        let prob = |x: f32, velocidad: f32, peso: f32, rng: &mut StdRng| {
+            probabilidad_captura(&con_presa(solo.clone(), x, velocidad, peso, rng), 0, 1, &terreno)
+        };
+
+        // Sube cuando la presa es mas lenta o mas liviana
+        let velocidades = [VEL_MAX_PRESA, VEL_MAX_PRESA / 2.0, VEL_MAX_PRESA / 4.0];
+        for par in velocidades.windows(2) {
+            assert!(prob(campo, par[1], 8.0, &mut rng) > prob(campo, par[0], 8.0, &mut rng));
+        }
+        for pesos in [[9.0, 6.0], [6.0, 4.5]] {
+            assert!(prob(campo, VEL_MAX_PRESA, pesos[1], &mut rng) > prob(campo, VEL_MAX_PRESA, pesos[0], &mut rng));
+        }
+        // Baja si la presa esta en el bosque, aunque ahi corra menos
+        for velocidad in [0.0, VEL_MAX_PRESA] {
+            assert!(prob(bosque, velocidad, 8.0, &mut rng) < prob(campo, velocidad, 8.0, &mut rng));
+        }
+
+        // Siempre en [0, 1], tambien con presas enfermas, quietas y livianas
+        for x in [campo, bosque] {
+            for velocidad in [0.0, VEL_MAX_PRESA, 10.0 * VEL_MAX_PRESA] {
+                for peso in [0.0, 1.0, 6.0, 50.0] {
+                    let mut mundo = con_presa(solo.clone(), x, velocidad, peso, &mut rng);
+                    for salud in [EstadoSalud::Sano, EstadoSalud::Enfermo] {
+                        mundo.salud[1].estado = salud;
+                        let prob = probabilidad_captura(&mundo, 0, 1, &terreno);
+                        assert!((0.0..=1.0).contains(&prob), "{} con velocidad {} y peso {}", prob, velocidad, peso);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn un_depredador_muerto_no_captura() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut vivo = encuentro(&mut rng);
+        let contadores = cazar(&mut vivo, &mut rng);
+        assert_eq!(contadores.muertes_por_predacion, 1);
+        assert_eq!(contadores.capturas_por_estrategia[EspecieDepredador::Zorro.estrategia().indice()], 1);
+
+        let mut muerto = encuentro(&mut rng);
+        muerto.matar(1);
+        let reserva = muerto.dietas[1].reserva;
+        assert_eq!(cazar(&mut muerto, &mut rng), ContadoresDiarios::default());
+        assert!(muerto.esta_vivo(0));
+        assert_eq!(muerto.dietas[1].reserva, reserva);
+        assert!(muerto.cazas[1].contactos.is_empty());
     }
-}
\ No newline at end of file
+}
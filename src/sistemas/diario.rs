@@ -1,52 +1,70 @@
 use ::rand::Rng;
-use crate::entidades::organismo::Organismo;
-use crate::entidades::{Presa, Depredador, EstadoSalud};
+use crate::entidades::{Mundo, EstadoSalud, EstrategiaCaza, Terreno};
 use crate::utilidades::configuraciones::*;
+use crate::sistemas::estaciones::factor_enfermedad;
 
-pub fn resetear_contadores_diarios(muertes_pred: &mut u32, muertes_enf: &mut u32,
-                               nuevos_infectados: &mut u32, recuperaciones: &mut u32) {
-    *muertes_pred = 0;
-    *muertes_enf = 0;
-    *nuevos_infectados = 0;
-    *recuperaciones = 0;
+// ==================== CONTADORES DIARIOS ====================
+/// Lo que los sistemas cuentan a lo largo del dia; la simulacion los pasa al reporte y los
+/// vuelve a cero en ese mismo momento
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ContadoresDiarios {
+    pub muertes_por_predacion: u32,
+    pub muertes_por_enfermedad: u32,
+    pub muertes_por_eventos: u32,
+    pub nuevos_infectados: u32,
+    pub recuperaciones: u32,
+    pub reproducciones: u32,
+    pub nacimientos_por_especie: [u32; 3],
+    pub emigraciones: u32,
+    pub capturas_por_estrategia: [u32; EstrategiaCaza::TODAS.len()],
+    pub ataques_fallidos: u32,
 }
 
 
-//Incrementa edad, Actualiza peso y Activa modo reproduccion si llego a la edad
-pub fn actualizar_presas_diarias(presas: &mut Vec<Presa>) {
-    for p in presas.iter_mut() {
-        p.set_edad(p.edad() + 1);
-        let (a, b, c) = p.especie().gompertz_params();
-        p.set_peso(a * (-b * f32::exp(-c * p.edad() as f32)).exp());
-        if p.edad() >= p.especie().edad_reproduccion() {
-            p.set_modo_reproduccion(true);
+//Incrementa edad, Actualiza peso y Activa modo reproduccion si llego a la edad (filas con Crecimiento y Especie)
+//El peso se acerca a la curva de Gompertz segun el valor de pastoreo de la celda y la vegetacion del dia
+pub fn actualizar_presas_diarias(mundo: &mut Mundo, terreno: &Terreno, vegetacion: f32) {
+    for (i, c) in mundo.crecimientos.iter_mut() {
+        let Some(especie) = mundo.especies.get(i) else { continue };
+        c.edad += 1;
+        let objetivo = especie.peso_gompertz(c.edad);
+        let pos = mundo.posiciones[i];
+        let pastoreo = terreno.celda_en(pos.x, pos.y).valor_pastoreo() * vegetacion;
+        c.peso += (objetivo - c.peso) * pastoreo;
+        if c.edad >= especie.edad_reproduccion()
+            && let Some(r) = mundo.reproducciones.get_mut(i)
+        {
+            r.activa = true;
         }
     }
 }
 
-//Puede enfermar, puede recuperar, si no se recupera muere
-pub fn procesar_enfermedad_presas(presas: &mut Vec<Presa>, rng: &mut impl Rng,
-                               nuevos_infectados: &mut u32, recuperaciones: &mut u32,
-                               muertes: &mut u32) {
-    for p in presas.iter_mut() {
-        if !p.esta_vivo() { continue; }
-        match p.salud() {
+//Puede enfermar (segun la estacion y si no esta vacunada), puede recuperar, si no se recupera muere
+//Filas con Salud y Especie: la enfermedad es de las presas
+pub fn procesar_enfermedad_presas(mundo: &mut Mundo, dia: u32, rng: &mut impl Rng, contadores: &mut ContadoresDiarios) {
+    let prob_enfermar = PROB_ENFERMAR_DIARIA_PRESA * factor_enfermedad(dia);
+    for (i, s) in mundo.salud.iter_mut() {
+        if !mundo.vivos[i] || !mundo.especies.tiene(i) { continue; }
+        if s.dias_inmune > 0 {
+            s.dias_inmune -= 1;
+        }
+        match s.estado {
             EstadoSalud::Sano => {
-                if rng.gen_range(0.0..1.0) < PROB_ENFERMAR_DIARIA_PRESA {
-                    p.set_salud(EstadoSalud::Enfermo);
-                    p.reset_dias_enfermo();
-                    *nuevos_infectados += 1;
+                if s.dias_inmune == 0 && rng.gen_range(0.0..1.0) < prob_enfermar {
+                    s.estado = EstadoSalud::Enfermo;
+                    s.dias_enfermo = 0;
+                    contadores.nuevos_infectados += 1;
                 }
             }
             EstadoSalud::Enfermo => {
-                p.incrementar_dias_enfermo();
+                s.dias_enfermo += 1;
                 if rng.gen_range(0.0..1.0) < PROB_RECUPERACION_DIARIA_PRESA {
-                    p.set_salud(EstadoSalud::Sano);
-                    p.reset_dias_enfermo();
-                    *recuperaciones += 1;
-                } else if p.dias_enfermo() >= MAX_DIAS_SIN_RECUPERAR_PRESA {
-                    p.matar();
-                    *muertes += 1;
+                    s.estado = EstadoSalud::Sano;
+                    s.dias_enfermo = 0;
+                    contadores.recuperaciones += 1;
+                } else if s.dias_enfermo >= MAX_DIAS_SIN_RECUPERAR_PRESA {
+                    mundo.vivos[i] = false;
+                    contadores.muertes_por_enfermedad += 1;
                 }
             }
         }
@@ -55,46 +73,50 @@ pub fn procesar_enfermedad_presas(presas: &mut Vec<Presa>, rng: &mut impl Rng,
 
 //Consumo diario, Consume segun umbrales, si cubre el mas alto sana si esta enfermo y si pasa variso dias sin sanar muere
 //filtro inmunidad para evitar enfermar en los primeros dias
-pub fn procesar_dietas_depredadores(depredadores: &mut Vec<Depredador>, dias: u32) {
-    for d in depredadores.iter_mut() {
-        if !d.esta_vivo() { continue; }
+//factor_consumo combina la estacion y los eventos (inviernos duros)
+//Filas con Dieta y Salud
+pub fn procesar_dietas_depredadores(mundo: &mut Mundo, dias: u32, factor_consumo: f32) {
+    let consumo = CONSUMO_DIARIO_DEPREDADOR * factor_consumo;
+    for (i, d) in mundo.dietas.iter_mut() {
+        let Some(s) = mundo.salud.get_mut(i) else { continue };
+        if !mundo.vivos[i] { continue; }
 
         // Consumo diario
-        if d.reserva() >= CONSUMO_DIARIO_DEPREDADOR {
-            d.set_reserva(d.reserva() - CONSUMO_DIARIO_DEPREDADOR);
+        if d.reserva >= consumo {
+            d.reserva -= consumo;
         } else {
-            d.set_reserva((d.reserva() - CONSUMO_DIARIO_DEPREDADOR).max(0.0));
+            d.reserva = (d.reserva - consumo).max(0.0);
         }
 
         if dias <= DIAS_INMUNIDAD {
-            d.set_salud(EstadoSalud::Sano);
-            d.reset_dias_enfermo();
+            s.estado = EstadoSalud::Sano;
+            s.dias_enfermo = 0;
             continue;
         }
 
-        if d.reserva() >= UMBRAL_OPTIMO_DEPREDADOR {
-            if d.salud() == EstadoSalud::Enfermo {
-                d.set_salud(EstadoSalud::Sano);
-                d.reset_dias_enfermo();
+        if d.reserva >= UMBRAL_OPTIMO_DEPREDADOR {
+            if s.estado == EstadoSalud::Enfermo {
+                s.estado = EstadoSalud::Sano;
+                s.dias_enfermo = 0;
             } else {
-                d.reset_dias_enfermo();
+                s.dias_enfermo = 0;
             }
-        } else if d.reserva() >= UMBRAL_MINIMO_DEPREDADOR {
-            d.reset_dias_enfermo();
-            d.set_salud(EstadoSalud::Sano);
-        } else if d.reserva() >= UMBRAL_DEFICIENTE_DEPREDADOR {
-            d.incrementar_dias_enfermo();
-            if d.dias_enfermo() > 2 {
-                d.set_salud(EstadoSalud::Enfermo);
+        } else if d.reserva >= UMBRAL_MINIMO_DEPREDADOR {
+            s.dias_enfermo = 0;
+            s.estado = EstadoSalud::Sano;
+        } else if d.reserva >= UMBRAL_DEFICIENTE_DEPREDADOR {
+            s.dias_enfermo += 1;
+            if s.dias_enfermo > 2 {
+                s.estado = EstadoSalud::Enfermo;
             }
-            if d.dias_enfermo() >= MAX_DIAS_SIN_RECUPERAR_DEPREDADOR {
-                d.matar();
+            if s.dias_enfermo >= MAX_DIAS_SIN_RECUPERAR_DEPREDADOR {
+                mundo.vivos[i] = false;
             }
         } else {
-            d.set_salud(EstadoSalud::Enfermo);
-            d.incrementar_dias_enfermo();
-            if d.dias_enfermo() >= MAX_DIAS_SIN_RECUPERAR_DEPREDADOR {
-                d.matar();
+            s.estado = EstadoSalud::Enfermo;
+            s.dias_enfermo += 1;
+            if s.dias_enfermo >= MAX_DIAS_SIN_RECUPERAR_DEPREDADOR {
+                mundo.vivos[i] = false;
             }
         }
     }
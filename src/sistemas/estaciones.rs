@@ -0,0 +1,62 @@
+use std::f32::consts::TAU;
+use crate::utilidades::configuraciones::*;
+
+// ==================== ESTACIONES ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Estacion { Primavera, Verano, Otono, Invierno }
+
+impl Estacion {
+    // El año empieza en primavera y cada estacion ocupa un cuarto
+    pub fn del_dia(dia: u32) -> Estacion {
+        match (fase_anual(dia) * 4.0) as u32 {
+            0 => Estacion::Primavera,
+            1 => Estacion::Verano,
+            2 => Estacion::Otono,
+            _ => Estacion::Invierno,
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Estacion::Primavera => "Primavera",
+            Estacion::Verano => "Verano",
+            Estacion::Otono => "Otoño",
+            Estacion::Invierno => "Invierno",
+        }
+    }
+}
+
+/// Posicion dentro del año en [0, 1)
+pub fn fase_anual(dia: u32) -> f32 {
+    (dia % DURACION_ANIO) as f32 / DURACION_ANIO as f32
+}
+
+// Coseno que vale 1 en el pico y -1 medio año despues
+fn onda(dia: u32, pico: f32) -> f32 {
+    (TAU * (fase_anual(dia) - pico)).cos()
+}
+
+/// Multiplicador del valor de pastoreo, en [1 - amplitud, 1]
+pub fn factor_vegetacion(dia: u32) -> f32 {
+    1.0 - AMPLITUD_VEGETACION * (1.0 - onda(dia, PICO_VEGETACION)) / 2.0
+}
+
+/// Multiplicador de la probabilidad diaria de enfermar de las presas
+pub fn factor_enfermedad(dia: u32) -> f32 {
+    1.0 + AMPLITUD_ENFERMEDAD * onda(dia, PICO_ENFERMEDAD)
+}
+
+/// Multiplicador del consumo diario de los depredadores
+pub fn factor_consumo_depredador(dia: u32) -> f32 {
+    1.0 + AMPLITUD_CONSUMO_DEPREDADOR * onda(dia, PICO_CONSUMO_DEPREDADOR)
+}
+
+/// Si la fase del año cae en la ventana [inicio, fin); admite ventanas que cruzan el fin de año
+pub fn en_ventana(dia: u32, (inicio, fin): (f32, f32)) -> bool {
+    let f = fase_anual(dia);
+    if inicio <= fin {
+        f >= inicio && f < fin
+    } else {
+        f >= inicio || f < fin
+    }
+}
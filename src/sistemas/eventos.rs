@@ -0,0 +1,273 @@
+use std::fs;
+use ::rand::Rng;
+use serde::Serialize;
+
+use crate::entidades::{Mundo, EstadoSalud, Terreno};
+use crate::sistemas::diario::ContadoresDiarios;
+use crate::sistemas::estaciones::Estacion;
+use crate::utilidades::configuraciones::*;
+
+// ==================== EVENTOS ====================
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TipoEvento {
+    Sequia { duracion: u32, factor: f32 },          // multiplica la vegetacion
+    InviernoDuro { duracion: u32, factor: f32 },    // multiplica el consumo de los depredadores
+    Inundacion { desborde: usize, fraccion: f32 },  // celdas de agua y las que estan a `desborde` celdas o menos
+    Brote { fraccion: f32 },
+}
+
+impl TipoEvento {
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            TipoEvento::Sequia { .. } => "Sequia",
+            TipoEvento::InviernoDuro { .. } => "Invierno duro",
+            TipoEvento::Inundacion { .. } => "Inundacion",
+            TipoEvento::Brote { .. } => "Brote",
+        }
+    }
+
+    fn detalle(&self) -> String {
+        match self {
+            TipoEvento::Sequia { duracion, factor } => format!("{} dias, vegetacion x{:.2}", duracion, factor),
+            TipoEvento::InviernoDuro { duracion, factor } => format!("{} dias, consumo x{:.2}", duracion, factor),
+            TipoEvento::Inundacion { desborde, fraccion } => format!("desborde {} celdas, mortalidad {:.2}", desborde, fraccion),
+            TipoEvento::Brote { fraccion } => format!("fraccion infectada {:.2}", fraccion),
+        }
+    }
+
+    // Formato: "<dia> sequia [duracion factor]", "<dia> invierno [duracion factor]",
+    // "<dia> inundacion [desborde fraccion]", "<dia> brote [fraccion]"
+    fn desde_partes(tipo: &str, p: &[f32]) -> Result<TipoEvento, String> {
+        let arg = |i: usize, defecto: f32| p.get(i).copied().unwrap_or(defecto);
+        match tipo {
+            "sequia" => Ok(TipoEvento::Sequia { duracion: arg(0, DURACION_SEQUIA as f32) as u32, factor: arg(1, FACTOR_SEQUIA) }),
+            "invierno" => Ok(TipoEvento::InviernoDuro { duracion: arg(0, DURACION_INVIERNO_DURO as f32) as u32, factor: arg(1, FACTOR_INVIERNO_DURO) }),
+            "inundacion" => Ok(TipoEvento::Inundacion { desborde: arg(0, DESBORDE_INUNDACION as f32) as usize, fraccion: arg(1, FRACCION_MUERTES_INUNDACION) }),
+            "brote" => Ok(TipoEvento::Brote { fraccion: arg(0, FRACCION_BROTE) }),
+            _ => Err(format!("tipo de evento desconocido '{}'", tipo)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EventoProgramado {
+    pub dia: u32,
+    pub tipo: TipoEvento,
+}
+
+/// Entrada del registro de eventos (tambien usada por las intervenciones de manejo)
+#[derive(Clone, Debug, Serialize)]
+pub struct RegistroEvento {
+    pub dia: u32,
+    pub evento: String,
+    pub origen: String,
+    pub afectados: u32,
+    pub detalle: String,
+}
+
+struct EventoActivo {
+    tipo: TipoEvento,
+    hasta: u32,
+}
+
+// ==================== PLANIFICADOR ====================
+pub struct Planificador {
+    guion: Vec<EventoProgramado>,
+    activos: Vec<EventoActivo>,
+    registro: Vec<RegistroEvento>,
+    aleatorios: bool,
+}
+
+impl Planificador {
+    pub fn new(guion: Vec<EventoProgramado>) -> Self {
+        Self { guion, activos: Vec::new(), registro: Vec::new(), aleatorios: true }
+    }
+
+    /// Solo el guion; para experimentos controlados
+    pub fn sin_aleatorios(guion: Vec<EventoProgramado>) -> Self {
+        Self { aleatorios: false, ..Self::new(guion) }
+    }
+
+    /// Lee el guion si el archivo existe; una linea por evento, '#' para comentarios
+    pub fn cargar_guion(ruta: &str) -> Result<Vec<EventoProgramado>, String> {
+        if !std::path::Path::new(ruta).exists() {
+            return Ok(Vec::new());
+        }
+        let texto = fs::read_to_string(ruta).map_err(|e| e.to_string())?;
+        let mut guion = Vec::new();
+        for (n, linea) in texto.lines().enumerate() {
+            let linea = linea.split('#').next().unwrap_or("").trim();
+            if linea.is_empty() { continue; }
+            let partes: Vec<&str> = linea.split_whitespace().collect();
+            let error = |e: String| format!("{} linea {}: {}", ruta, n + 1, e);
+            let dia = partes[0].parse::<u32>().map_err(|e| error(e.to_string()))?;
+            let tipo = partes.get(1).ok_or_else(|| error("falta el tipo de evento".to_string()))?;
+            let params = partes[2..].iter()
+                .map(|s| s.parse::<f32>())
+                .collect::<Result<Vec<f32>, _>>()
+                .map_err(|e| error(e.to_string()))?;
+            guion.push(EventoProgramado { dia, tipo: TipoEvento::desde_partes(tipo, &params).map_err(error)? });
+        }
+        Ok(guion)
+    }
+
+    pub fn registro(&self) -> &[RegistroEvento] { &self.registro }
+
+    pub fn registrar(&mut self, entrada: RegistroEvento) {
+        self.registro.push(entrada);
+    }
+
+    /// Multiplicador de vegetacion de las sequias activas
+    pub fn factor_vegetacion(&self) -> f32 {
+        self.activos.iter()
+            .map(|a| match a.tipo { TipoEvento::Sequia { factor, .. } => factor, _ => 1.0 })
+            .product()
+    }
+
+    /// Multiplicador del consumo de los depredadores por inviernos duros activos
+    pub fn factor_consumo(&self) -> f32 {
+        self.activos.iter()
+            .map(|a| match a.tipo { TipoEvento::InviernoDuro { factor, .. } => factor, _ => 1.0 })
+            .product()
+    }
+
+    // Dispara los eventos del guion y los aleatorios del dia y aplica sus efectos inmediatos
+    pub fn procesar_dia(&mut self, dia: u32, mundo: &mut Mundo, terreno: &Terreno, rng: &mut impl Rng,
+                        contadores: &mut ContadoresDiarios) {
+        self.activos.retain(|a| a.hasta > dia);
+
+        let mut del_dia: Vec<(TipoEvento, &str)> = self.guion.iter()
+            .filter(|e| e.dia == dia)
+            .map(|e| (e.tipo, "guion"))
+            .collect();
+
+        if self.aleatorios {
+            if rng.gen_range(0.0..1.0) < PROB_SEQUIA {
+                del_dia.push((TipoEvento::Sequia { duracion: DURACION_SEQUIA, factor: FACTOR_SEQUIA }, "aleatorio"));
+            }
+            if Estacion::del_dia(dia) == Estacion::Invierno && rng.gen_range(0.0..1.0) < PROB_INVIERNO_DURO {
+                del_dia.push((TipoEvento::InviernoDuro { duracion: DURACION_INVIERNO_DURO, factor: FACTOR_INVIERNO_DURO }, "aleatorio"));
+            }
+            if rng.gen_range(0.0..1.0) < PROB_INUNDACION {
+                del_dia.push((TipoEvento::Inundacion { desborde: DESBORDE_INUNDACION, fraccion: FRACCION_MUERTES_INUNDACION }, "aleatorio"));
+            }
+            if rng.gen_range(0.0..1.0) < PROB_BROTE {
+                del_dia.push((TipoEvento::Brote { fraccion: FRACCION_BROTE }, "aleatorio"));
+            }
+        }
+
+        for (tipo, origen) in del_dia {
+            let afectados = match tipo {
+                TipoEvento::Sequia { duracion, .. } | TipoEvento::InviernoDuro { duracion, .. } => {
+                    self.activos.push(EventoActivo { tipo, hasta: dia + duracion });
+                    0
+                }
+                // Solo en las celdas que alcanza el agua (a cualquier entidad con Posicion);
+                // un mapa sin agua no se inunda
+                TipoEvento::Inundacion { desborde, fraccion } => {
+                    let mut n = 0;
+                    for (i, pos) in mundo.posiciones.iter() {
+                        if mundo.vivos[i] && terreno.inundable(pos.x, pos.y, desborde) && rng.gen_range(0.0..1.0) < fraccion {
+                            mundo.vivos[i] = false;
+                            n += 1;
+                        }
+                    }
+                    contadores.muertes_por_eventos += n;
+                    n
+                }
+                // Contagia a las presas: filas con Salud y Especie
+                TipoEvento::Brote { fraccion } => {
+                    let mut n = 0;
+                    for (i, s) in mundo.salud.iter_mut() {
+                        if mundo.vivos[i] && mundo.especies.tiene(i) && s.estado == EstadoSalud::Sano && s.dias_inmune == 0
+                            && rng.gen_range(0.0..1.0) < fraccion {
+                            s.estado = EstadoSalud::Enfermo;
+                            s.dias_enfermo = 0;
+                            n += 1;
+                        }
+                    }
+                    contadores.nuevos_infectados += n;
+                    n
+                }
+            };
+            self.registrar(RegistroEvento {
+                dia,
+                evento: tipo.nombre().to_string(),
+                origen: origen.to_string(),
+                afectados,
+                detalle: tipo.detalle(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::SeedableRng;
+    use ::rand::rngs::StdRng;
+    use crate::entidades::{Especie, plantilla_presa, plantilla_depredador};
+
+    // Guarda el texto en un archivo temporal propio de la prueba y lo lee como guion
+    fn guion(nombre: &str, texto: &str) -> Result<Vec<EventoProgramado>, String> {
+        let ruta = std::env::temp_dir().join(format!("guion_{}_{}.txt", nombre, std::process::id()));
+        fs::write(&ruta, texto).unwrap();
+        let guion = Planificador::cargar_guion(ruta.to_str().unwrap());
+        let _ = fs::remove_file(&ruta);
+        guion
+    }
+
+    #[test]
+    fn lee_cada_tipo_de_evento() {
+        let eventos = guion("tipos", "# comentario\n\n5 sequia 10 0.5\n6 sequia\n7 invierno 3 2.0  # duro\n8 inundacion 1 0.25\n9 brote 0.1\n10 brote\n").unwrap();
+        let leidos: Vec<(u32, TipoEvento)> = eventos.iter().map(|e| (e.dia, e.tipo)).collect();
+        assert_eq!(leidos, vec![
+            (5, TipoEvento::Sequia { duracion: 10, factor: 0.5 }),
+            (6, TipoEvento::Sequia { duracion: DURACION_SEQUIA, factor: FACTOR_SEQUIA }),
+            (7, TipoEvento::InviernoDuro { duracion: 3, factor: 2.0 }),
+            (8, TipoEvento::Inundacion { desborde: 1, fraccion: 0.25 }),
+            (9, TipoEvento::Brote { fraccion: 0.1 }),
+            (10, TipoEvento::Brote { fraccion: FRACCION_BROTE }),
+        ]);
+        assert_eq!(Planificador::cargar_guion("eventos_ejemplo.txt").unwrap().len(), 4);
+        assert!(Planificador::cargar_guion("no_existe.txt").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rechaza_lineas_invalidas_con_su_numero() {
+        for (linea, motivo) in [("tormenta 3", "invalid digit"), ("4 tormenta", "desconocido 'tormenta'"),
+                                ("4", "falta el tipo"), ("4 sequia diez", "invalid float")] {
+            let error = guion("errores", &format!("# guion\n1 brote\n{}\n", linea)).unwrap_err();
+            assert!(error.contains(".txt linea 3: "), "{}", error);
+            assert!(error.contains(motivo), "'{}' deberia mencionar '{}'", error, motivo);
+        }
+    }
+
+    #[test]
+    fn la_sequia_dura_lo_programado_y_la_inundacion_solo_mata_en_el_agua() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut planificador = Planificador::sin_aleatorios(vec![
+            EventoProgramado { dia: 2, tipo: TipoEvento::Sequia { duracion: 3, factor: 0.5 } },
+            EventoProgramado { dia: 3, tipo: TipoEvento::Inundacion { desborde: 0, fraccion: 1.0 } },
+        ]);
+        // Agua a la izquierda y campo a la derecha
+        let terreno = Terreno::desde_texto("~.").unwrap();
+        let (agua, campo) = (ANCHO_MUNDO * 0.25, ANCHO_MUNDO * 0.75);
+        let mut mundo = Mundo::default();
+        for x in [agua, agua, campo] {
+            mundo.crear(plantilla_presa(x, ALTO_MUNDO / 2.0, Especie::Raton, &mut rng));
+        }
+        mundo.crear(plantilla_depredador(campo, ALTO_MUNDO / 2.0, crate::entidades::EspecieDepredador::Zorro, &mut rng));
+        let mut contadores = ContadoresDiarios::default();
+        let mut factores = Vec::new();
+        for dia in 1..=6 {
+            planificador.procesar_dia(dia, &mut mundo, &terreno, &mut rng, &mut contadores);
+            factores.push(planificador.factor_vegetacion());
+        }
+        assert_eq!(factores, vec![1.0, 0.5, 0.5, 0.5, 1.0, 1.0]);
+        assert_eq!((0..4).map(|i| mundo.esta_vivo(i)).collect::<Vec<_>>(), vec![false, false, true, true]);
+        assert_eq!((contadores.muertes_por_eventos, contadores.nuevos_infectados), (2, 0));
+        let afectados: Vec<(&str, u32)> = planificador.registro().iter().map(|r| (r.evento.as_str(), r.afectados)).collect();
+        assert_eq!(afectados, vec![("Sequia", 0), ("Inundacion", 2)]);
+    }
+}
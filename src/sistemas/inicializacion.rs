@@ -1,22 +1,33 @@
 use ::rand::Rng;
-use crate::entidades::{Presa, Depredador,Especie};
-use macroquad::prelude::*;
+use crate::entidades::{Mundo, Especie, EspecieDepredador, Terreno, TipoCelda, plantilla_presa, plantilla_depredador};
+use crate::utilidades::configuraciones::{ANCHO_MUNDO, ALTO_MUNDO};
 
 
-pub fn inicializar_presas(especies: &[Especie], cantidad: usize, rng: &mut impl Rng) -> Vec<Presa> {
-    (0..cantidad).map(|_| {
+// Posicion al azar sobre una celda transitable (con un limite de intentos)
+pub fn posicion_libre(terreno: &Terreno, factor: fn(&TipoCelda) -> f32, rng: &mut impl Rng) -> (f32, f32) {
+    let mut pos = (rng.gen_range(0.0..ANCHO_MUNDO), rng.gen_range(0.0..ALTO_MUNDO));
+    for _ in 0..100 {
+        if factor(&terreno.celda_en(pos.0, pos.1)) > 0.0 {
+            break;
+        }
+        pos = (rng.gen_range(0.0..ANCHO_MUNDO), rng.gen_range(0.0..ALTO_MUNDO));
+    }
+    pos
+}
+
+pub fn inicializar_presas(mundo: &mut Mundo, especies: &[Especie], cantidad: usize, terreno: &Terreno, rng: &mut impl Rng) {
+    for _ in 0..cantidad {
         let especie = especies[rng.gen_range(0..especies.len())];
-        Presa::new(
-            rng.gen_range(0.0..screen_width()),
-            rng.gen_range(0.0..screen_height()),
-            especie,
-        )
-    }).collect()
+        let (x, y) = posicion_libre(terreno, TipoCelda::factor_velocidad_presa, rng);
+        mundo.crear(plantilla_presa(x, y, especie, rng));
+    }
 }
 
-pub fn inicializar_depredadores(cantidad: usize, rng: &mut impl Rng) -> Vec<Depredador> {
-    (0..cantidad).map(|_| Depredador::new(
-        rng.gen_range(0.0..screen_width()),
-        rng.gen_range(0.0..screen_height())
-    )).collect()
+pub fn inicializar_depredadores(mundo: &mut Mundo, especies: &[EspecieDepredador], cantidad: usize, terreno: &Terreno,
+                                rng: &mut impl Rng) {
+    for _ in 0..cantidad {
+        let especie = especies[rng.gen_range(0..especies.len())];
+        let (x, y) = posicion_libre(terreno, TipoCelda::factor_velocidad_depredador, rng);
+        mundo.crear(plantilla_depredador(x, y, especie, rng));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,273 @@
+use std::fs;
+use ::rand::Rng;
+use ::rand::seq::SliceRandom;
+
+use crate::entidades::{Especie, EspecieDepredador, EstadoSalud, plantilla_presa, plantilla_depredador};
+use crate::entidades::componentes::{Crecimiento, Dieta, Reproduccion};
+use crate::sistemas::Simulacion;
+use crate::sistemas::eventos::RegistroEvento;
+use crate::utilidades::configuraciones::*;
+
+// ==================== INTERVENCIONES DE MANEJO ====================
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Intervencion {
+    Sacrificio { especie: Especie, cantidad: usize },
+    LiberarPresas { especie: Especie, cantidad: usize, x: f32, y: f32 },
+    LiberarDepredadores { especie: EspecieDepredador, cantidad: usize, x: f32, y: f32 },
+    Vacunar { especie: Especie, fraccion: f32, dias: u32 },
+    TratarDepredadores,
+}
+
+impl Intervencion {
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Intervencion::Sacrificio { .. } => "Sacrificio",
+            Intervencion::LiberarPresas { .. } => "Liberacion de presas",
+            Intervencion::LiberarDepredadores { .. } => "Liberacion de depredadores",
+            Intervencion::Vacunar { .. } => "Vacunacion",
+            Intervencion::TratarDepredadores => "Tratamiento de depredadores",
+        }
+    }
+
+    fn detalle(&self) -> String {
+        match self {
+            Intervencion::Sacrificio { especie, cantidad } => format!("{} x{}", especie.nombre(), cantidad),
+            Intervencion::LiberarPresas { especie, cantidad, x, y } => format!("{} x{} en ({:.0}, {:.0})", especie.nombre(), cantidad, x, y),
+            Intervencion::LiberarDepredadores { especie, cantidad, x, y } => format!("{} x{} en ({:.0}, {:.0})", especie.nombre(), cantidad, x, y),
+            Intervencion::Vacunar { especie, fraccion, dias } => format!("{} fraccion {:.2}, {} dias", especie.nombre(), fraccion, dias),
+            Intervencion::TratarDepredadores => "depredadores enfermos".to_string(),
+        }
+    }
+
+    // Formato: "<dia> sacrificar <especie> <n>", "<dia> liberar_presas <especie> <n> <x> <y>",
+    // "<dia> liberar_depredadores <n> <x> <y> [especie]", "<dia> vacunar <especie> <fraccion> <dias>",
+    // "<dia> tratar_depredadores"
+    fn desde_partes(partes: &[&str]) -> Result<Intervencion, String> {
+        let especie = |i: usize| -> Result<Especie, String> {
+            let nombre = partes.get(i).ok_or("falta la especie")?;
+            Especie::desde_nombre(nombre).ok_or(format!("especie desconocida '{}'", nombre))
+        };
+        let num = |i: usize| -> Result<f32, String> {
+            partes.get(i).ok_or(format!("falta el parametro {}", i))?
+                .parse::<f32>().map_err(|e| e.to_string())
+        };
+        match partes.first().copied() {
+            Some("sacrificar") => Ok(Intervencion::Sacrificio { especie: especie(1)?, cantidad: num(2)? as usize }),
+            Some("liberar_presas") => Ok(Intervencion::LiberarPresas { especie: especie(1)?, cantidad: num(2)? as usize, x: num(3)?, y: num(4)? }),
+            Some("liberar_depredadores") => {
+                let especie = match partes.get(4) {
+                    Some(nombre) => EspecieDepredador::desde_nombre(nombre).ok_or(format!("especie de depredador desconocida '{}'", nombre))?,
+                    None => EspecieDepredador::Zorro,
+                };
+                Ok(Intervencion::LiberarDepredadores { especie, cantidad: num(1)? as usize, x: num(2)?, y: num(3)? })
+            }
+            Some("vacunar") => Ok(Intervencion::Vacunar { especie: especie(1)?, fraccion: num(2)?, dias: num(3)? as u32 }),
+            Some("tratar_depredadores") => Ok(Intervencion::TratarDepredadores),
+            Some(otra) => Err(format!("intervencion desconocida '{}'", otra)),
+            None => Err("falta la intervencion".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IntervencionProgramada {
+    pub dia: u32,
+    pub intervencion: Intervencion,
+}
+
+/// Lee el escenario si el archivo existe; una linea por intervencion, '#' para comentarios
+pub fn cargar_escenario(ruta: &str) -> Result<Vec<IntervencionProgramada>, String> {
+    if !std::path::Path::new(ruta).exists() {
+        return Ok(Vec::new());
+    }
+    let texto = fs::read_to_string(ruta).map_err(|e| e.to_string())?;
+    let mut escenario = Vec::new();
+    for (n, linea) in texto.lines().enumerate() {
+        let linea = linea.split('#').next().unwrap_or("").trim();
+        if linea.is_empty() { continue; }
+        let partes: Vec<&str> = linea.split_whitespace().collect();
+        let error = |e: String| format!("{} linea {}: {}", ruta, n + 1, e);
+        let dia = partes[0].parse::<u32>().map_err(|e| error(e.to_string()))?;
+        let intervencion = Intervencion::desde_partes(&partes[1..]).map_err(error)?;
+        escenario.push(IntervencionProgramada { dia, intervencion });
+    }
+    Ok(escenario)
+}
+
+// Aplica la intervencion en el dia actual, la registra como evento y devuelve cuantos organismos afecto
+pub fn aplicar_intervencion(sim: &mut Simulacion, intervencion: Intervencion, origen: &str) -> u32 {
+    let afectados = match intervencion {
+        Intervencion::Sacrificio { especie, cantidad } => {
+            let mundo = &mut sim.mundo;
+            let mut candidatas: Vec<usize> = mundo.especies.iter()
+                .filter(|&(i, &e)| mundo.vivos[i] && e == especie)
+                .map(|(i, _)| i)
+                .collect();
+            candidatas.shuffle(&mut sim.rng);
+            let n = candidatas.len().min(cantidad);
+            for &i in &candidatas[..n] {
+                mundo.matar(i);
+            }
+            sim.contadores.muertes_por_eventos += n as u32;
+            n
+        }
+        Intervencion::LiberarPresas { especie, cantidad, x, y } => {
+            // Se liberan adultos listos para reproducirse
+            let edad = especie.edad_reproduccion();
+            for _ in 0..cantidad {
+                let dx = sim.rng.gen_range(-RADIO_APARICION_CRIA..RADIO_APARICION_CRIA);
+                let dy = sim.rng.gen_range(-RADIO_APARICION_CRIA..RADIO_APARICION_CRIA);
+                let mut p = plantilla_presa(x + dx, y + dy, especie, &mut sim.rng);
+                p.crecimiento = Some(Crecimiento { edad, peso: especie.peso_gompertz(edad) });
+                p.reproduccion = p.reproduccion.map(|r| Reproduccion { activa: true, ..r });
+                sim.mundo.crear(p);
+            }
+            cantidad
+        }
+        Intervencion::LiberarDepredadores { especie, cantidad, x, y } => {
+            for _ in 0..cantidad {
+                let mut d = plantilla_depredador(x, y, especie, &mut sim.rng);
+                d.dieta = Some(Dieta { reserva: UMBRAL_MINIMO_DEPREDADOR });
+                if let Some(b) = d.bitacora.as_mut() {
+                    b.iniciar_dia(UMBRAL_MINIMO_DEPREDADOR, true);
+                }
+                sim.mundo.crear(d);
+            }
+            cantidad
+        }
+        Intervencion::Vacunar { especie, fraccion, dias } => {
+            let mut n = 0;
+            let mundo = &mut sim.mundo;
+            for (i, s) in mundo.salud.iter_mut() {
+                if mundo.vivos[i] && mundo.especies.get(i) == Some(&especie) && s.estado == EstadoSalud::Sano
+                    && sim.rng.gen_range(0.0..1.0) < fraccion {
+                    s.dias_inmune = dias;
+                    n += 1;
+                }
+            }
+            n
+        }
+        Intervencion::TratarDepredadores => {
+            let mut n = 0;
+            let mundo = &mut sim.mundo;
+            for (i, s) in mundo.salud.iter_mut() {
+                if mundo.vivos[i] && mundo.especies_depredador.tiene(i) && s.estado == EstadoSalud::Enfermo {
+                    s.estado = EstadoSalud::Sano;
+                    s.dias_enfermo = 0;
+                    n += 1;
+                }
+            }
+            n
+        }
+    } as u32;
+
+    sim.planificador.registrar(RegistroEvento {
+        dia: sim.dias,
+        evento: intervencion.nombre().to_string(),
+        origen: origen.to_string(),
+        afectados,
+        detalle: intervencion.detalle(),
+    });
+    afectados
+}
+
+/// Ejecuta las intervenciones del escenario programadas para el dia actual
+pub fn procesar_escenario(sim: &mut Simulacion) {
+    let del_dia: Vec<Intervencion> = sim.escenario.iter()
+        .filter(|i| i.dia == sim.dias)
+        .map(|i| i.intervencion)
+        .collect();
+    for intervencion in del_dia {
+        aplicar_intervencion(sim, intervencion, "escenario");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entidades::Mundo;
+    use crate::sistemas::eventos::Planificador;
+
+    // Guarda el texto en un archivo temporal propio de la prueba y lo lee como escenario
+    fn escenario(nombre: &str, texto: &str) -> Result<Vec<IntervencionProgramada>, String> {
+        let ruta = std::env::temp_dir().join(format!("escenario_{}_{}.txt", nombre, std::process::id()));
+        fs::write(&ruta, texto).unwrap();
+        let escenario = cargar_escenario(ruta.to_str().unwrap());
+        let _ = fs::remove_file(&ruta);
+        escenario
+    }
+
+    #[test]
+    fn lee_cada_intervencion() {
+        let texto = "# manejo\n1 sacrificar raton 10\n2 liberar_presas ardilla 6 200 150\n3 liberar_depredadores 2 400 300\n\
+                     4 vacunar conejo 0.6 40  # mitad\n5 tratar_depredadores\n";
+        let leidas: Vec<(u32, Intervencion)> = escenario("verbos", texto).unwrap().iter().map(|i| (i.dia, i.intervencion)).collect();
+        assert_eq!(leidas, vec![
+            (1, Intervencion::Sacrificio { especie: Especie::Raton, cantidad: 10 }),
+            (2, Intervencion::LiberarPresas { especie: Especie::Ardilla, cantidad: 6, x: 200.0, y: 150.0 }),
+            (3, Intervencion::LiberarDepredadores { especie: EspecieDepredador::Zorro, cantidad: 2, x: 400.0, y: 300.0 }),
+            (4, Intervencion::Vacunar { especie: Especie::Conejo, fraccion: 0.6, dias: 40 }),
+            (5, Intervencion::TratarDepredadores),
+        ]);
+
+        // Cada especie de depredador por su nombre
+        for especie in EspecieDepredador::TODAS {
+            let texto = format!("7 liberar_depredadores 1 10 20 {}\n", especie.nombre().to_lowercase());
+            let leida = escenario("depredadores", &texto).unwrap()[0].intervencion;
+            assert_eq!(leida, Intervencion::LiberarDepredadores { especie, cantidad: 1, x: 10.0, y: 20.0 });
+        }
+        assert_eq!(cargar_escenario("escenario_ejemplo.txt").unwrap().len(), 6);
+    }
+
+    #[test]
+    fn rechaza_lineas_invalidas_con_su_numero() {
+        for (linea, motivo) in [("cinco sacrificar raton 1", "invalid digit"), ("4 cazar raton 1", "desconocida 'cazar'"),
+                                ("4 sacrificar topo 1", "especie desconocida 'topo'"), ("4 liberar_presas conejo 2 100", "falta el parametro"),
+                                ("4 liberar_depredadores 1 10 20 oso", "depredador desconocida 'oso'")] {
+            let error = escenario("errores", &format!("1 tratar_depredadores\n\n{}\n", linea)).unwrap_err();
+            assert!(error.contains(".txt linea 3: "), "{}", error);
+            assert!(error.contains(motivo), "'{}' deberia mencionar '{}'", error, motivo);
+        }
+    }
+
+    #[test]
+    fn el_sacrificio_quita_n_de_la_especie_y_la_liberacion_agrega_n_en_el_punto() {
+        let mut sim = Simulacion::new(6);
+        sim.mundo = Mundo::default();
+        for i in 0..12 {
+            let especie = if i < 8 { Especie::Raton } else { Especie::Conejo };
+            sim.mundo.crear(plantilla_presa(100.0, 100.0, especie, &mut sim.rng));
+        }
+        sim.planificador = Planificador::sin_aleatorios(Vec::new());
+        sim.dias = 1;
+
+        let n = aplicar_intervencion(&mut sim, Intervencion::Sacrificio { especie: Especie::Raton, cantidad: 5 }, "prueba");
+        let vivas = |m: &Mundo, e: Especie| m.especies.iter().filter(|&(i, &x)| m.esta_vivo(i) && x == e).count();
+        assert_eq!((n, sim.contadores.muertes_por_eventos), (5, 5));
+        assert_eq!((vivas(&sim.mundo, Especie::Raton), vivas(&sim.mundo, Especie::Conejo)), (3, 4));
+        // No se sacrifican mas de las que hay
+        let n = aplicar_intervencion(&mut sim, Intervencion::Sacrificio { especie: Especie::Raton, cantidad: 5 }, "prueba");
+        assert_eq!((n, sim.contadores.muertes_por_eventos, vivas(&sim.mundo, Especie::Raton)), (3, 8, 0));
+
+        sim.dias = 2;
+        let n = aplicar_intervencion(&mut sim, Intervencion::LiberarPresas { especie: Especie::Ardilla, cantidad: 4, x: 300.0, y: 250.0 }, "prueba");
+        assert_eq!((n, sim.mundo.len()), (4, 16));
+        for i in 12..16 {
+            let pos = sim.mundo.posiciones[i];
+            assert_eq!(sim.mundo.especies[i], Especie::Ardilla);
+            assert!((pos.x - 300.0).abs() <= RADIO_APARICION_CRIA && (pos.y - 250.0).abs() <= RADIO_APARICION_CRIA);
+            assert_eq!(sim.mundo.crecimientos[i].edad, Especie::Ardilla.edad_reproduccion());
+            assert!(sim.mundo.reproducciones[i].activa);
+        }
+
+        sim.dias = 3;
+        let n = aplicar_intervencion(&mut sim, Intervencion::LiberarDepredadores { especie: EspecieDepredador::Halcon, cantidad: 2, x: 50.0, y: 60.0 }, "prueba");
+        let liberados: Vec<usize> = sim.mundo.especies_depredador.filas().collect();
+        assert_eq!((n, liberados), (2, vec![16, 17]));
+        assert!((16..18).all(|i| sim.mundo.especies_depredador[i] == EspecieDepredador::Halcon
+            && (sim.mundo.posiciones[i].x, sim.mundo.posiciones[i].y) == (50.0, 60.0)));
+
+        let registro: Vec<(u32, &str, u32)> = sim.planificador.registro().iter().map(|r| (r.dia, r.evento.as_str(), r.afectados)).collect();
+        assert_eq!(registro, vec![(1, "Sacrificio", 5), (1, "Sacrificio", 3), (2, "Liberacion de presas", 4), (3, "Liberacion de depredadores", 2)]);
+    }
+}
@@ -4,9 +4,21 @@ pub mod movimiento;
 pub mod reproduccion;
 pub mod depredacion;
 pub mod colision;
+pub mod bordes;
+pub mod estaciones;
+pub mod ciclo_diario;
+pub mod eventos;
+pub mod intervenciones;
+pub mod simulacion;
+pub mod respuesta_funcional;
+pub mod campo_medio;
+pub mod paralelo;
+pub mod rendimiento;
 
 pub use inicializacion::{inicializar_presas, inicializar_depredadores};
-pub use diario::{resetear_contadores_diarios, actualizar_presas_diarias, procesar_enfermedad_presas, procesar_dietas_depredadores};
-pub use movimiento::{movimiento_presas, depredadores_buscar_presas};
+pub use diario::{ContadoresDiarios, actualizar_presas_diarias, procesar_enfermedad_presas, procesar_dietas_depredadores};
+pub use movimiento::{movimiento_presas, depredadores_buscar_presas, avanzar};
 pub use reproduccion::reproduccion;
-pub use depredacion::depredadores_comer;
+pub use depredacion::{depredadores_comer, gasto_persecucion};
+pub use bordes::aplicar_borde;
+pub use simulacion::Simulacion;
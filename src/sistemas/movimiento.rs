@@ -1,80 +1,238 @@
 use ::rand::Rng;
-use crate::entidades::organismo::Organismo;
-use crate::entidades::{Presa, Depredador};
+use crate::entidades::componentes::{Posicion, Velocidad, Locomocion};
+use crate::entidades::{Mundo, Entidad, Terreno, TipoCelda, EstrategiaCaza};
 use crate::utilidades::configuraciones::*;
+use crate::sistemas::bordes::distancia;
+use crate::sistemas::estaciones::en_ventana;
+use crate::sistemas::ciclo_diario::{actividad, Periodo};
+use crate::sistemas::paralelo::{Flujos, IndiceEspacial, mapear};
 
 
-// Busca pareja si esta en modo_reproduccuin o se mueve aleatoriamente
-pub fn movimiento_presas(presas: &mut Vec<Presa>, rng: &mut impl Rng) {
-    for i in 0..presas.len() {
-        let p = &presas[i];
-        if !p.esta_vivo() || !p.modo_reproduccion() || p.cooldown() > 0.0 {
-            continue;
-        }
+// Busca pareja si esta en modo_reproduccuin (y en temporada) o se mueve aleatoriamente.
+// Consulta las filas con Reproduccion y Especie (ademas de Posicion, Velocidad, Locomocion y Espera).
+// Todas deciden sobre el estado al empezar el paso y despues se aplican las decisiones
+pub fn movimiento_presas(mundo: &mut Mundo, dia: u32, flujos: Flujos, paralelo: bool) {
+    let filas: Vec<usize> = mundo.reproducciones.filas().filter(|&i| mundo.especies.tiene(i)).collect();
+    let lectura: &Mundo = mundo;
+    let decisiones = mapear(filas.len(), paralelo, |k| decidir_movimiento(lectura, &filas, k, dia, flujos));
+    for (&i, decision) in filas.iter().zip(decisiones) {
+        let pareja = match decision {
+            Some(Movimiento::HaciaPareja { tx, ty, id }) => {
+                let rapidez = mundo.locomociones[i].rapidez;
+                mundo.velocidades[i].hacia(mundo.posiciones[i], tx, ty, rapidez);
+                Some(id)
+            }
+            Some(Movimiento::Aleatorio { vx, vy }) => {
+                mundo.velocidades[i] = Velocidad { vx, vy };
+                None
+            }
+            None => None,
+        };
+        mundo.reproducciones[i].pareja = pareja;
+    }
+}
 
-        // Buscar pareja más cercana
-        let mut pareja_index: Option<usize> = None;
-        let mut dist_min = f32::MAX;
+// Lo que decide una presa en un paso
+enum Movimiento {
+    HaciaPareja { tx: f32, ty: f32, id: Entidad },
+    Aleatorio { vx: f32, vy: f32 },
+}
 
-        for j in 0..presas.len() {
+// None si la presa filas[k] no busca pareja en este paso (sigue con su velocidad)
+fn decidir_movimiento(mundo: &Mundo, filas: &[usize], k: usize, dia: u32, flujos: Flujos) -> Option<Movimiento> {
+    let i = filas[k];
+    let (p, especie) = (mundo.reproducciones[i], mundo.especies[i]);
+    if !mundo.esta_vivo(i) || !p.activa || mundo.esperas[i].restante > 0.0 {
+        return None;
+    }
+    let mut rng = flujos.de(k);
+    let pos = mundo.posiciones[i];
+
+    // Buscar pareja más cercana
+    let mut pareja_index: Option<usize> = None;
+    if en_ventana(dia, especie.ventana_reproduccion()) {
+        let mut dist_min = f32::MAX;
+        for &j in filas {
             if i == j { continue; }
-            let q = &presas[j];
-            if !q.esta_vivo() || q.especie() != p.especie() || q.sexo() == p.sexo() {
+            let q = mundo.reproducciones[j];
+            if !mundo.esta_vivo(j) || mundo.especies[j] != especie || q.sexo == p.sexo {
                 continue;
             }
-            if !q.modo_reproduccion() || q.cooldown() > 0.0 { continue; }
+            if !q.activa || mundo.esperas[j].restante > 0.0 { continue; }
 
-            let dx = q.x() - p.x();
-            let dy = q.y() - p.y();
-            let dist = (dx*dx + dy*dy).sqrt();
+            let otra = mundo.posiciones[j];
+            let dist = distancia(pos.x, pos.y, otra.x, otra.y);
 
             if dist < dist_min {
                 dist_min = dist;
                 pareja_index = Some(j);
             }
         }
+    }
 
-        if let Some(j) = pareja_index {
-            let pareja = &presas[j];
-            let tx = pareja.x() + rng.gen_range(-RADIO_PRESA..RADIO_PRESA);
-            let ty = pareja.y() + rng.gen_range(-RADIO_PRESA..RADIO_PRESA);
-            presas[i].mover_hacia(tx, ty);
-        } else {
-            mover_aleatoriamente(&mut presas[i], rng);
+    Some(match pareja_index {
+        Some(j) => {
+            let pareja = mundo.posiciones[j];
+            let tx = pareja.x + rng.gen_range(-RADIO_PRESA..RADIO_PRESA);
+            let ty = pareja.y + rng.gen_range(-RADIO_PRESA..RADIO_PRESA);
+            Movimiento::HaciaPareja { tx, ty, id: mundo.id(j) }
         }
-    }
+        None => {
+            let (vx, vy) = velocidad_aleatoria(mundo.velocidades[i], mundo.locomociones[i].rapidez, &mut rng);
+            Movimiento::Aleatorio { vx, vy }
+        }
+    })
 }
 
-pub fn mover_aleatoriamente(p: &mut Presa, rng: &mut impl Rng) {
-    let mut vx = p.vx() + rng.gen_range(-RUIDO_MOVIMIENTO..RUIDO_MOVIMIENTO);
-    let mut vy = p.vy() + rng.gen_range(-RUIDO_MOVIMIENTO..RUIDO_MOVIMIENTO);
+pub fn velocidad_aleatoria(v: Velocidad, rapidez: f32, rng: &mut impl Rng) -> (f32, f32) {
+    let mut vx = v.vx + rng.gen_range(-RUIDO_MOVIMIENTO..RUIDO_MOVIMIENTO);
+    let mut vy = v.vy + rng.gen_range(-RUIDO_MOVIMIENTO..RUIDO_MOVIMIENTO);
     let vel = (vx.powi(2) + vy.powi(2)).sqrt();
-    if vel > VEL_MAX_PRESA {
-        vx = vx / vel * VEL_MAX_PRESA;
-        vy = vy / vel * VEL_MAX_PRESA;
+    if vel > rapidez {
+        vx = vx / vel * rapidez;
+        vy = vy / vel * rapidez;
     }
-    p.set_vx(vx);
-    p.set_vy(vy);
+    (vx, vy)
 }
 
+// Avanza segun la velocidad, el terreno y la actividad del periodo; rebota contra las celdas intransitables
+pub fn avanzar(pos: Posicion, vel: Velocidad, loc: &Locomocion, terreno: &Terreno, periodo: Periodo) -> (Posicion, Velocidad) {
+    let transitable = |x: f32, y: f32| loc.factor(terreno.celda_en(x, y)) > 0.0;
+    let Posicion { x, y } = pos;
+    let Velocidad { mut vx, mut vy } = vel;
+    // Si ya esta sobre una celda intransitable (p. ej. una cria) se le deja salir
+    let libre = !transitable(x, y);
+    let f = if libre { 1.0 } else { loc.factor(terreno.celda_en(x, y)) } * actividad(loc.actividad, periodo);
+
+    let mut nx = x + vx * f;
+    if !libre && !transitable(nx, y) {
+        vx = -vx;
+        nx = x;
+    }
+    let mut ny = y + vy * f;
+    if !libre && !transitable(nx, ny) {
+        vy = -vy;
+        ny = y;
+    }
+    (Posicion { x: nx, y: ny }, Velocidad { vx, vy })
+}
+
+// Una presa en una madriguera no puede ser vista ni atrapada
+pub fn presa_escondida(pos: Posicion, terreno: &Terreno) -> bool {
+    terreno.celda_en(pos.x, pos.y) == TipoCelda::Madriguera
+}
 
-// Busca presas que pasen la edad de sacrificio y la que sea mas pesada
-pub fn depredadores_buscar_presas(depredadores: &mut Vec<Depredador>, presas: &[Presa]) {
-    for d in depredadores.iter_mut() {
-        if let Some(obj) = presas.iter()
-            .filter(|p| p.esta_vivo() && p.edad() >= p.especie().edad_sacrificio())
-            .min_by(|a, b| {
-                let cmp_peso = b.peso_actual().partial_cmp(&a.peso_actual()).unwrap();
-                if cmp_peso == std::cmp::Ordering::Equal {
-                    let dist_a = (a.x() - d.x()).powi(2) + (a.y() - d.y()).powi(2);
-                    let dist_b = (b.x() - d.x()).powi(2) + (b.y() - d.y()).powi(2);
-                    dist_a.partial_cmp(&dist_b).unwrap()
-                } else {
-                    cmp_peso
-                }
-            })
-        {
-            d.mover_hacia(obj.x(), obj.y());
+pub fn presa_visible(desde: Posicion, pos: Posicion, terreno: &Terreno) -> bool {
+    let visibilidad = terreno.celda_en(pos.x, pos.y).visibilidad();
+    !presa_escondida(pos, terreno)
+        && distancia(desde.x, desde.y, pos.x, pos.y) <= RADIO_VISION_DEPREDADOR * visibilidad
+}
+
+// Lo que decide un cazador en un paso
+enum Rumbo {
+    Hacia { tx: f32, ty: f32, id: Entidad },
+    Quieto,
+    Sigue,
+}
+
+// Elige presa entre las visibles que pasen la edad de sacrificio segun la estrategia de su Caza
+// (la visibilidad no pasa de 1, asi que basta con mirar el indice hasta RADIO_VISION_DEPREDADOR).
+// Consulta las filas con Caza; el indice tiene las filas de las presas. Un cazador muerto suelta
+// el objetivo y se queda quieto
+pub fn depredadores_buscar_presas(mundo: &mut Mundo, terreno: &Terreno, indice: &IndiceEspacial, paralelo: bool) {
+    let filas: Vec<usize> = mundo.cazas.filas().collect();
+    let lectura: &Mundo = mundo;
+    let rumbos = mapear(filas.len(), paralelo, |k| elegir_presa(lectura, filas[k], terreno, indice));
+    for (&i, rumbo) in filas.iter().zip(rumbos) {
+        let objetivo = match rumbo {
+            Rumbo::Hacia { tx, ty, id } => {
+                let rapidez = mundo.locomociones[i].rapidez;
+                mundo.velocidades[i].hacia(mundo.posiciones[i], tx, ty, rapidez);
+                Some(id)
+            }
+            Rumbo::Quieto => {
+                mundo.velocidades[i].detener();
+                None
+            }
+            Rumbo::Sigue => None,
+        };
+        mundo.cazas[i].objetivo = objetivo;
+    }
+}
+
+fn elegir_presa(mundo: &Mundo, i: usize, terreno: &Terreno, indice: &IndiceEspacial) -> Rumbo {
+    if !mundo.esta_vivo(i) {
+        return Rumbo::Quieto;
+    }
+    let estrategia = mundo.cazas[i].estrategia;
+    let pos = mundo.posiciones[i];
+    let dist = |j: &usize| {
+        let p = mundo.posiciones[*j];
+        distancia(pos.x, pos.y, p.x, p.y)
+    };
+    let peso = |j: &usize| mundo.crecimientos[*j].peso;
+    let cercanas = indice.cercanos(pos.x, pos.y, RADIO_VISION_DEPREDADOR);
+    let candidatas = cercanas.into_iter()
+        .filter(|&j| mundo.esta_vivo(j) && mundo.crecimientos[j].edad >= mundo.especies[j].edad_sacrificio())
+        .filter(|&j| presa_visible(pos, mundo.posiciones[j], terreno));
+
+    let objetivo = match estrategia {
+        EstrategiaCaza::MasPesada => candidatas.min_by(|a, b| {
+            let cmp_peso = peso(b).partial_cmp(&peso(a)).unwrap();
+            if cmp_peso == std::cmp::Ordering::Equal {
+                dist(a).partial_cmp(&dist(b)).unwrap()
+            } else {
+                cmp_peso
+            }
+        }),
+        EstrategiaCaza::MasCercana | EstrategiaCaza::Persecucion => candidatas
+            .min_by(|a, b| dist(a).partial_cmp(&dist(b)).unwrap()),
+        EstrategiaCaza::ForrajeoOptimo => candidatas
+            .max_by(|a, b| {
+                let ganancia = |j: &usize| peso(j) / dist(j).max(1.0);
+                ganancia(a).partial_cmp(&ganancia(b)).unwrap()
+            }),
+        EstrategiaCaza::Emboscada => candidatas
+            .filter(|j| dist(j) <= RADIO_EMBOSCADA)
+            .min_by(|a, b| dist(a).partial_cmp(&dist(b)).unwrap()),
+    };
+
+    match objetivo {
+        Some(j) => {
+            let (obj, v) = (mundo.posiciones[j], mundo.velocidades[j]);
+            let id = mundo.id(j);
+            if estrategia == EstrategiaCaza::Persecucion {
+                // Punto de intercepcion: donde estara la presa cuando el cazador llegue
+                let t = dist(&j) / mundo.locomociones[i].rapidez;
+                Rumbo::Hacia { tx: obj.x + v.vx * t, ty: obj.y + v.vy * t, id }
+            } else {
+                Rumbo::Hacia { tx: obj.x, ty: obj.y, id }
+            }
         }
+        None if estrategia == EstrategiaCaza::Emboscada => Rumbo::Quieto,
+        None => Rumbo::Sigue,
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::SeedableRng;
+    use ::rand::rngs::StdRng;
+    use crate::entidades::{Especie, EspecieDepredador, plantilla_presa, plantilla_depredador};
+
+    #[test]
+    fn un_depredador_muerto_no_persigue() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut mundo = Mundo::default();
+        let presa = mundo.crear(plantilla_presa(120.0, 100.0, Especie::Conejo, &mut rng));
+        mundo.crecimientos[0].edad = Especie::Conejo.edad_sacrificio();
+        mundo.crear(plantilla_depredador(100.0, 100.0, EspecieDepredador::Zorro, &mut rng));
+        mundo.crear(plantilla_depredador(100.0, 100.0, EspecieDepredador::Zorro, &mut rng));
+        mundo.matar(2);
+        let indice = IndiceEspacial::new([(0, mundo.posiciones[0])], TAMANO_CELDA_INDICE);
+        depredadores_buscar_presas(&mut mundo, &Terreno::abierto(), &indice, false);
+        assert_eq!(mundo.cazas[1].objetivo, Some(presa));
+        assert_eq!(mundo.cazas[2].objetivo, None);
+        assert_eq!(mundo.velocidades[2], Velocidad { vx: 0.0, vy: 0.0 });
+    }
+}
@@ -0,0 +1,97 @@
+use ::rand::rngs::StdRng;
+use ::rand::SeedableRng;
+use rayon::prelude::*;
+
+use crate::entidades::componentes::Posicion;
+use crate::utilidades::configuraciones::*;
+
+// ==================== FLUJOS ALEATORIOS ====================
+// Cada organismo tiene su propio generador en cada paso, que depende solo de la semilla, el
+// paso y su indice: el resultado no cambia con el orden en que los hilos lo procesan
+#[derive(Clone, Copy, Debug)]
+pub struct Flujos {
+    pub semilla: u64,
+    pub paso: u64,
+}
+
+// Mezclador de splitmix64: semillas vecinas dan generadores sin relacion
+fn mezclar(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Flujos {
+    pub fn de(&self, indice: usize) -> StdRng {
+        StdRng::seed_from_u64(mezclar(mezclar(self.semilla ^ mezclar(self.paso)) ^ indice as u64))
+    }
+}
+
+// ==================== SERIAL O PARALELO ====================
+/// f(i) para i en 0..n, en orden o repartido entre hilos; el resultado queda en orden de indice
+pub fn mapear<R: Send>(n: usize, paralelo: bool, f: impl Fn(usize) -> R + Sync + Send) -> Vec<R> {
+    if paralelo {
+        (0..n).into_par_iter().map(f).collect()
+    } else {
+        (0..n).map(f).collect()
+    }
+}
+
+// ==================== INDICE ESPACIAL ====================
+// Rejilla uniforme con las filas del mundo que caen en cada celda; se arma en cada paso y
+// responde quienes pueden estar a menos de un radio de un punto (vivos o no)
+pub struct IndiceEspacial {
+    columnas: usize,
+    filas: usize,
+    ancho: f32,
+    alto: f32,
+    celdas: Vec<Vec<usize>>,
+}
+
+impl IndiceEspacial {
+    pub fn new(puntos: impl IntoIterator<Item = (usize, Posicion)>, tamano_celda: f32) -> Self {
+        let columnas = ((ANCHO_MUNDO / tamano_celda).ceil() as usize).max(1);
+        let filas = ((ALTO_MUNDO / tamano_celda).ceil() as usize).max(1);
+        let mut indice = Self {
+            columnas,
+            filas,
+            ancho: ANCHO_MUNDO / columnas as f32,
+            alto: ALTO_MUNDO / filas as f32,
+            celdas: vec![Vec::new(); columnas * filas],
+        };
+        for (i, pos) in puntos {
+            let c = ((pos.x / indice.ancho).max(0.0) as usize).min(columnas - 1);
+            let f = ((pos.y / indice.alto).max(0.0) as usize).min(filas - 1);
+            indice.celdas[f * columnas + c].push(i);
+        }
+        indice
+    }
+
+    /// Filas, de menor a mayor, de las celdas que toca el circulo; falta filtrar por distancia
+    pub fn cercanos(&self, x: f32, y: f32, radio: f32) -> Vec<usize> {
+        let columnas = rango(x, radio, self.ancho, self.columnas);
+        let filas = rango(y, radio, self.alto, self.filas);
+        let mut indices = Vec::new();
+        for &f in &filas {
+            for &c in &columnas {
+                indices.extend_from_slice(&self.celdas[f * self.columnas + c]);
+            }
+        }
+        indices.sort_unstable();
+        indices
+    }
+}
+
+// Celdas de un eje entre centro - radio y centro + radio; en modo toroidal dan la vuelta
+fn rango(centro: f32, radio: f32, tamano: f32, n: usize) -> Vec<usize> {
+    let desde = ((centro - radio) / tamano).floor() as i64;
+    let hasta = ((centro + radio) / tamano).floor() as i64;
+    if hasta.saturating_sub(desde) >= n as i64 - 1 {
+        (0..n).collect()
+    } else if MODO_BORDE == ModoBorde::Toroidal {
+        (desde..=hasta).map(|k| k.rem_euclid(n as i64) as usize).collect()
+    } else {
+        (desde.max(0)..=hasta.min(n as i64 - 1)).map(|k| k as usize).collect()
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Instant;
+use crate::entidades::Especie;
+use crate::entidades::componentes::Crecimiento;
+use crate::sistemas::Simulacion;
+use crate::sistemas::inicializacion::inicializar_presas;
+use crate::utilidades::configuraciones::*;
+
+// ==================== RENDIMIENTO DEL PASO ====================
+/// Tiempo medio de un paso con una cantidad de hilos (0 = camino serial)
+#[derive(Clone, Copy, Debug)]
+pub struct MedicionRendimiento {
+    pub hilos: usize,
+    pub ms_por_paso: f64,
+    pub aceleracion: f64, // respecto del camino serial
+}
+
+// Reemplaza las presas por `cantidad` adultas listas para reproducirse, asi el paso
+// trabaja con una poblacion grande desde el primer dia
+pub fn poblar_adultas(sim: &mut Simulacion, cantidad: usize) {
+    sim.mundo.retener(|m, i| !m.especies.tiene(i));
+    inicializar_presas(&mut sim.mundo, &Especie::TODAS, cantidad, &sim.terreno, &mut sim.rng);
+    let mundo = &mut sim.mundo;
+    for (i, especie) in mundo.especies.iter() {
+        let edad = especie.edad_reproduccion();
+        mundo.crecimientos[i] = Crecimiento { edad, peso: especie.peso_gompertz(edad) };
+        mundo.reproducciones[i].activa = true;
+    }
+}
+
+// Misma poblacion inicial (adultos) para cada medicion, primero en serie y luego con 1, 2, 4...
+// hilos hasta los nucleos disponibles; cada corrida da el mismo resultado, solo cambia el tiempo
+pub fn medir_rendimiento(presas: usize, pasos: u32, semilla: u64) -> Result<Vec<MedicionRendimiento>, String> {
+    let nucleos = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut hilos = vec![0];
+    let mut h = 1;
+    while h < nucleos {
+        hilos.push(h);
+        h *= 2;
+    }
+    hilos.push(nucleos);
+
+    let mut mediciones: Vec<MedicionRendimiento> = Vec::new();
+    for h in hilos {
+        let mut sim = Simulacion::new(semilla);
+        sim.paralelo = h > 0;
+        poblar_adultas(&mut sim, presas);
+        let grupo = rayon::ThreadPoolBuilder::new().num_threads(h.max(1)).build().map_err(|e| e.to_string())?;
+        let inicio = Instant::now();
+        grupo.install(|| {
+            for _ in 0..pasos {
+                sim.paso(PASO_SIN_INTERFAZ);
+            }
+        });
+        let ms_por_paso = inicio.elapsed().as_secs_f64() * 1000.0 / pasos.max(1) as f64;
+        let serial = mediciones.first().map_or(ms_por_paso, |m| m.ms_por_paso);
+        mediciones.push(MedicionRendimiento { hilos: h, ms_por_paso, aceleracion: serial / ms_por_paso });
+    }
+    Ok(mediciones)
+}
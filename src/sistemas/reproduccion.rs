@@ -1,52 +1,79 @@
 use ::rand::Rng;
-use ::rand::thread_rng;
-use crate::entidades::{Presa, Especie};
+use crate::entidades::{Mundo, Plantilla, plantilla_presa};
+use crate::entidades::presa::num_crias;
 use crate::utilidades::configuraciones::*;
 use crate::sistemas::colision::colision;
+use crate::sistemas::diario::ContadoresDiarios;
+use crate::sistemas::estaciones::en_ventana;
+use crate::sistemas::paralelo::{IndiceEspacial, mapear};
 
-pub fn reproduccion(presas: &mut Vec<Presa>, especies: &[Especie], reproducciones_diarias: &mut u32) {
-    let mut nuevas_presas: Vec<Presa> = Vec::new();
-    let mut rng2 = thread_rng();
-    let mut parejas_repro: Vec<(usize, usize, Especie)> = Vec::new();
-
-    for i in 0..presas.len() {
-        for j in (i + 1)..presas.len() {
-            if !presas[i].esta_vivo() || !presas[j].esta_vivo() { continue; }
-            if presas[i].especie() != presas[j].especie() { continue; }
-            if presas[i].cooldown() > 0.0 || presas[j].cooldown() > 0.0 { continue; }
-            if presas[i].edad() < presas[i].especie().edad_reproduccion() ||
-               presas[j].edad() < presas[j].especie().edad_reproduccion() { continue; }
-            if (presas[i].sexo() == presas[j].sexo()) { continue; }
-            if !colision(&presas[i], &presas[j]) { continue; }
-
-            let especie = presas[i].especie();
-            let count_actual = presas.iter().filter(|p| p.especie() == especie).count();
+// Parejas que se tocan y pueden reproducirse, entre las filas con Reproduccion, Especie y
+// Crecimiento: se buscan en paralelo con el indice espacial y luego se recorren en orden (i, j)
+// con el generador de la simulacion, asi las crias salen siempre iguales y en el mismo orden
+pub fn reproduccion(mundo: &mut Mundo, dia: u32, indice: &IndiceEspacial, paralelo: bool, rng: &mut impl Rng,
+                    contadores: &mut ContadoresDiarios) {
+    let filas: Vec<usize> = mundo.reproducciones.filas()
+        .filter(|&i| mundo.especies.tiene(i) && mundo.crecimientos.tiene(i))
+        .collect();
+    let lectura: &Mundo = mundo;
+    let parejas_por_presa = mapear(filas.len(), paralelo, |k| parejas_de(lectura, filas[k], dia, indice));
+
+    // La poblacion de cada especie no cambia hasta agregar las crias
+    let mut poblacion = [0usize; 3];
+    for (_, especie) in mundo.especies.iter() {
+        poblacion[especie.indice()] += 1;
+    }
+
+    let mut crias: Vec<Plantilla> = Vec::new();
+    let mut parejas_repro: Vec<(usize, usize)> = Vec::new();
+    for (&i, parejas) in filas.iter().zip(parejas_por_presa) {
+        for j in parejas {
+            let especie = mundo.especies[i];
             let max_pobl = especie.poblacion_maxima();
             // Posibles multiples crias
-            let n_crias = Presa::num_crias(especie, &mut rng2);
-            let espacio = max_pobl.saturating_sub(count_actual);
+            let n_crias = num_crias(especie, rng);
+            let espacio = max_pobl.saturating_sub(poblacion[especie.indice()]);
             let n_a_crear = n_crias.min(espacio);
 
+            let pos = mundo.posiciones[i];
             for _ in 0..n_a_crear {
-                let dx = rng2.gen_range(-RADIO_APARICION_CRIA..RADIO_APARICION_CRIA);
-                let dy = rng2.gen_range(-RADIO_APARICION_CRIA..RADIO_APARICION_CRIA);
-                nuevas_presas.push(Presa::crear_cria(
-                    presas[i].x() + dx,
-                    presas[i].y() + dy,
-                    especie,
-                    &mut rng2
-                ));
+                let dx = rng.gen_range(-RADIO_APARICION_CRIA..RADIO_APARICION_CRIA);
+                let dy = rng.gen_range(-RADIO_APARICION_CRIA..RADIO_APARICION_CRIA);
+                crias.push(plantilla_presa(pos.x + dx, pos.y + dy, especie, rng));
             }
 
-            if n_a_crear > 0 { *reproducciones_diarias += 1; }
-            parejas_repro.push((i, j, especie));
+            if n_a_crear > 0 { contadores.reproducciones += 1; }
+            contadores.nacimientos_por_especie[especie.indice()] += n_a_crear as u32;
+            parejas_repro.push((i, j));
         }
     }
 
-    presas.extend(nuevas_presas);
+    // Las crias van al final: las filas de los padres no cambian
+    for cria in crias {
+        mundo.crear(cria);
+    }
+
+    for (i, j) in parejas_repro {
+        mundo.esperas[i].restante = 2.0;
+        mundo.esperas[j].restante = 2.0;
+    }
+}
 
-    for (i, j, _) in parejas_repro {
-        presas[i].set_cooldown(2.0);
-        presas[j].set_cooldown(2.0);
+// Filas j > i con las que i se puede reproducir en este paso, de menor a mayor
+fn parejas_de(mundo: &Mundo, i: usize, dia: u32, indice: &IndiceEspacial) -> Vec<usize> {
+    let especie = mundo.especies[i];
+    if !mundo.esta_vivo(i) || mundo.esperas[i].restante > 0.0 || mundo.crecimientos[i].edad < especie.edad_reproduccion()
+        || !en_ventana(dia, especie.ventana_reproduccion()) {
+        return Vec::new();
     }
-}
\ No newline at end of file
+    let pos = mundo.posiciones[i];
+    let sexo = mundo.reproducciones[i].sexo;
+    indice.cercanos(pos.x, pos.y, 2.0 * RADIO_PRESA).into_iter()
+        .filter(|&j| j > i)
+        .filter(|&j| {
+            mundo.esta_vivo(j) && mundo.especies.get(j) == Some(&especie) && mundo.esperas[j].restante <= 0.0
+                && mundo.crecimientos[j].edad >= especie.edad_reproduccion() && mundo.reproducciones[j].sexo != sexo
+                && colision(mundo, i, j)
+        })
+        .collect()
+}
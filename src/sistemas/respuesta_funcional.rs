@@ -0,0 +1,77 @@
+use ::rand::Rng;
+use crate::entidades::{Mundo, Especie, EspecieDepredador, EstadoSalud, TipoCelda, plantilla_presa};
+use crate::entidades::componentes::Crecimiento;
+use crate::sistemas::Simulacion;
+use crate::sistemas::eventos::Planificador;
+use crate::sistemas::inicializacion::{inicializar_depredadores, posicion_libre};
+use crate::utilidades::configuraciones::*;
+use crate::utilidades::holling::PuntoRespuesta;
+
+// ==================== EXPERIMENTO DE RESPUESTA FUNCIONAL ====================
+// Para cada cantidad de presas se corre una simulacion sin interfaz reponiendo las presas
+// capturadas, asi la densidad queda fija; los depredadores se mantienen sanos y alimentados
+// para que solo cuenten la busqueda y la manipulacion (TIEMPO_ESPERA_COMIDA y persecucion)
+pub fn medir_respuesta_funcional(cantidades: &[usize], dias: u32, depredadores: usize,
+                                 especie: EspecieDepredador, semilla: u64) -> Vec<PuntoRespuesta> {
+    let area = ANCHO_MUNDO * ALTO_MUNDO / AREA_UNIDAD_DENSIDAD;
+    cantidades.iter().map(|&cantidad| {
+        let mut sim = Simulacion::new(semilla);
+        sim.escenario.clear();
+        sim.planificador = Planificador::sin_aleatorios(Vec::new());
+        sim.mundo = Mundo::default();
+        reponer_presas(&mut sim, cantidad);
+        inicializar_depredadores(&mut sim.mundo, &[especie], depredadores, &sim.terreno, &mut sim.rng);
+
+        let mut capturas = 0u32;
+        let mut fallidos = 0u32;
+        let mut depredador_dias = 0u32;
+        while sim.dias < dias + DIAS_CALENTAMIENTO_RESPUESTA {
+            if sim.paso(PASO_SIN_INTERFAZ) {
+                mantener_depredadores(&mut sim);
+                if sim.dias > DIAS_CALENTAMIENTO_RESPUESTA {
+                    let r = sim.reportes.last().unwrap();
+                    capturas += r.muertes_por_predacion;
+                    fallidos += r.ataques_fallidos;
+                    depredador_dias += r.depredadores_vivos as u32;
+                }
+            }
+            reponer_presas(&mut sim, cantidad);
+        }
+
+        let por_depredador_dia = |n: u32| if depredador_dias > 0 { n as f32 / depredador_dias as f32 } else { 0.0 };
+        PuntoRespuesta {
+            presas: cantidad,
+            densidad: cantidad as f32 / area,
+            dias,
+            capturas_por_depredador_dia: por_depredador_dia(capturas),
+            ataques_fallidos_por_depredador_dia: por_depredador_dia(fallidos),
+        }
+    }).collect()
+}
+
+// Completa con presas adultas (capturables) o descarta las crias sobrantes
+fn reponer_presas(sim: &mut Simulacion, cantidad: usize) {
+    let especies = [Especie::Conejo, Especie::Raton, Especie::Ardilla];
+    let corte = sim.mundo.especies.filas().nth(cantidad);
+    if let Some(corte) = corte {
+        sim.mundo.retener(|m, i| i < corte || !m.especies.tiene(i));
+    }
+    for _ in sim.mundo.especies.filas().count()..cantidad {
+        let especie = especies[sim.rng.gen_range(0..especies.len())];
+        let (x, y) = posicion_libre(&sim.terreno, TipoCelda::factor_velocidad_presa, &mut sim.rng);
+        let mut p = plantilla_presa(x, y, especie, &mut sim.rng);
+        let edad = especie.edad_sacrificio();
+        p.crecimiento = Some(Crecimiento { edad, peso: especie.peso_gompertz(edad) });
+        sim.mundo.crear(p);
+    }
+}
+
+fn mantener_depredadores(sim: &mut Simulacion) {
+    let mundo = &mut sim.mundo;
+    for (i, dieta) in mundo.dietas.iter_mut().filter(|(i, _)| mundo.vivos[*i]) {
+        dieta.reserva = UMBRAL_OPTIMO_DEPREDADOR;
+        let salud = &mut mundo.salud[i];
+        salud.estado = EstadoSalud::Sano;
+        salud.dias_enfermo = 0;
+    }
+}
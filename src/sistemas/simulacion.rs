@@ -0,0 +1,368 @@
+use std::collections::HashSet;
+use std::path::Path;
+use ::rand::rngs::StdRng;
+use ::rand::SeedableRng;
+use crate::entidades::{Mundo, Entidad, Especie, EspecieDepredador, EstadoSalud, Terreno};
+use crate::sistemas::*;
+use crate::sistemas::ciclo_diario::Periodo;
+use crate::sistemas::estaciones::{factor_vegetacion, factor_consumo_depredador};
+use crate::sistemas::eventos::Planificador;
+use crate::sistemas::bordes::distancia;
+use crate::sistemas::paralelo::{Flujos, IndiceEspacial, mapear};
+use crate::sistemas::intervenciones::{Intervencion, IntervencionProgramada, cargar_escenario, procesar_escenario, aplicar_intervencion};
+use crate::utilidades::*;
+use crate::utilidades::reportes::{ClasePiramide, RegistroDepredador, compilar_piramide_edades, compilar_bitacora_depredadores};
+use crate::utilidades::densidad::{CapaDensidad, MapaDensidad};
+use crate::utilidades::salidas::{FormatoReportes, MetadatosCorrida, SalidaReportes, crear_salida};
+
+// ==================== SIMULACION ====================
+// Estado completo del mundo; avanza sin dibujar, asi sirve con ventana y sin interfaz
+pub struct Simulacion {
+    pub rng: StdRng,
+    pub semilla: u64,
+    pub metadatos: MetadatosCorrida,
+    pub terreno: Terreno,
+    pub mundo: Mundo,
+    pub dias: u32,
+    pub tiempo_acumulado: f32,
+    pub reportes: Vec<EstadisticasDiarias>,
+    pub planificador: Planificador,
+    pub escenario: Vec<IntervencionProgramada>,
+    pub registrar_piramide: bool,
+    pub piramide_edades: Vec<ClasePiramide>,
+    pub bitacora_depredadores: Vec<RegistroDepredador>,
+    pub densidad: MapaDensidad,
+    pub paralelo: bool,
+    pasos: u64,
+
+    // Salida incremental: filas ya escritas de reportes, eventos, piramide y bitacora
+    salida: Option<Box<dyn SalidaReportes>>,
+    reportes_escritos: usize,
+    eventos_escritos: usize,
+    piramide_escrita: usize,
+    bitacora_escrita: usize,
+
+    pub contadores: ContadoresDiarios,
+}
+
+impl Simulacion {
+    /// Mundo inicial con terreno, guion de eventos y escenario leidos de las rutas configuradas
+    pub fn new(semilla: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(semilla);
+        let especies = [Especie::Conejo, Especie::Raton, Especie::Ardilla];
+        let especies_depredador = [EspecieDepredador::Zorro];
+
+        let terreno = Terreno::cargar_o_abierto(RUTA_TERRENO);
+        let mut mundo = Mundo::default();
+        inicializar_presas(&mut mundo, &especies, PRESAS_INICIALES, &terreno, &mut rng);
+        inicializar_depredadores(&mut mundo, &especies_depredador, DEPREDADORES_INICIALES, &terreno, &mut rng);
+
+        let guion = Planificador::cargar_guion(RUTA_GUION_EVENTOS).unwrap_or_else(|e| {
+            eprintln!("Error cargando guion de eventos: {}", e);
+            Vec::new()
+        });
+        let escenario = cargar_escenario(RUTA_ESCENARIO).unwrap_or_else(|e| {
+            eprintln!("Error cargando escenario: {}", e);
+            Vec::new()
+        });
+
+        Self {
+            rng,
+            semilla,
+            metadatos: MetadatosCorrida::new(semilla),
+            terreno,
+            mundo,
+            dias: 0,
+            tiempo_acumulado: 0.0,
+            reportes: Vec::new(),
+            planificador: Planificador::new(guion),
+            escenario,
+            salida: None,
+            registrar_piramide: PIRAMIDE_EDADES,
+            piramide_edades: Vec::new(),
+            bitacora_depredadores: Vec::new(),
+            densidad: MapaDensidad::new(COLUMNAS_DENSIDAD, FILAS_DENSIDAD, DIAS_DENSIDAD),
+            paralelo: PASO_PARALELO,
+            pasos: 0,
+            reportes_escritos: 0,
+            eventos_escritos: 0,
+            piramide_escrita: 0,
+            bitacora_escrita: 0,
+            contadores: ContadoresDiarios::default(),
+        }
+    }
+
+    pub fn periodo(&self) -> Periodo {
+        Periodo::actual(self.tiempo_acumulado)
+    }
+
+    /// Avanza dt segundos; devuelve true si en este paso termino un dia
+    pub fn paso(&mut self, dt: f32) -> bool {
+        self.tiempo_acumulado += dt;
+        let nuevo_dia = self.tiempo_acumulado >= DURACION_DIA;
+        if nuevo_dia {
+            self.dias += 1;
+            self.tiempo_acumulado = 0.0;
+            self.procesar_dia();
+            self.escribir_salida();
+        }
+        let periodo = self.periodo();
+        let flujos = Flujos { semilla: self.semilla, paso: self.pasos };
+        self.pasos += 1;
+        // ==================== Movimiento inteligente ====================
+        let indice = indice_presas(&self.mundo);
+        movimiento_presas(&mut self.mundo, self.dias, flujos, self.paralelo);
+        depredadores_buscar_presas(&mut self.mundo, &self.terreno, &indice, self.paralelo);
+        gasto_persecucion(&mut self.mundo, dt);
+        // Actualizar organismos
+        actualizar_organismos(&mut self.mundo, &self.terreno, periodo, dt, self.paralelo, &mut self.contadores);
+        // Reproducción (las presas ya se movieron: indice nuevo)
+        let indice = indice_presas(&self.mundo);
+        reproduccion(&mut self.mundo, self.dias, &indice, self.paralelo, &mut self.rng, &mut self.contadores);
+        // Depredadores comen (las presas que mueren aqui son capturas)
+        let vivos = self.mundo.vivos.clone();
+        depredadores_comer(&mut self.mundo, &self.terreno, periodo, &mut self.rng, &mut self.contadores);
+        for (i, pos) in self.mundo.posiciones.iter().filter(|&(i, _)| self.mundo.especies.tiene(i)) {
+            if vivos[i] && !self.mundo.esta_vivo(i) {
+                self.densidad.registrar(CapaDensidad::Capturas, pos.x, pos.y);
+            }
+        }
+        // Limpiar los muertos; los que llevan Bitacora esperan al cierre del dia
+        self.mundo.retener(|m, i| m.esta_vivo(i) || m.bitacoras.tiene(i));
+        nuevo_dia
+    }
+
+    // Procesos diarios y reporte del dia
+    fn procesar_dia(&mut self) {
+        let dias = self.dias;
+        // Las presas enfermas que no lo estaban al empezar son las infecciones del dia (brotes incluidos)
+        let enfermas: HashSet<Entidad> = self.presas_enfermas().collect();
+        self.planificador.procesar_dia(dias, &mut self.mundo, &self.terreno, &mut self.rng, &mut self.contadores);
+        procesar_escenario(self);
+        actualizar_presas_diarias(&mut self.mundo, &self.terreno, factor_vegetacion(dias) * self.planificador.factor_vegetacion());
+        procesar_enfermedad_presas(&mut self.mundo, dias, &mut self.rng, &mut self.contadores);
+        let nuevas: Vec<Entidad> = self.presas_enfermas().filter(|id| !enfermas.contains(id)).collect();
+        for id in nuevas {
+            let pos = self.mundo.posiciones[self.mundo.fila(id).unwrap()];
+            self.densidad.registrar(CapaDensidad::Infecciones, pos.x, pos.y);
+        }
+        procesar_dietas_depredadores(&mut self.mundo, dias, factor_consumo_depredador(dias) * self.planificador.factor_consumo());
+        // Guardar reporte diario: lo contado pasa al reporte y los contadores quedan en cero para el dia siguiente
+        let contadores = std::mem::take(&mut self.contadores);
+        self.reportes.push(compilar_reporte_diario(&self.mundo, &contadores, dias));
+        if self.registrar_piramide {
+            self.piramide_edades.extend(compilar_piramide_edades(&self.mundo, dias));
+        }
+        self.bitacora_depredadores.extend(compilar_bitacora_depredadores(&mut self.mundo, dias));
+        // En modo absorbente los que emigraron o murieron salen de la simulacion, ya con su ultima fila en la bitacora
+        if MODO_BORDE == ModoBorde::Absorbente {
+            self.mundo.retener(|m, i| m.esta_vivo(i) || !m.bitacoras.tiene(i));
+        }
+        self.densidad.cerrar_dia(&self.mundo);
+    }
+
+    // Ids de las presas (filas con Especie) enfermas
+    fn presas_enfermas(&self) -> impl Iterator<Item = Entidad> + '_ {
+        self.mundo.salud.iter()
+            .filter(|&(i, s)| self.mundo.especies.tiene(i) && s.estado == EstadoSalud::Enfermo)
+            .map(|(i, _)| self.mundo.id(i))
+    }
+
+    /// Intervencion de manejo fuera del escenario (teclado)
+    pub fn intervenir(&mut self, intervencion: Intervencion, origen: &str) {
+        aplicar_intervencion(self, intervencion, origen);
+        self.escribir_salida();
+    }
+
+    /// Empieza a escribir los reportes a medida que se generan (con los metadatos como encabezado)
+    pub fn abrir_salida(&mut self, formato: FormatoReportes) -> Result<(), String> {
+        self.abrir_salida_en(formato, Path::new("."))
+    }
+
+    /// Igual que abrir_salida pero con los archivos dentro de `directorio`
+    pub fn abrir_salida_en(&mut self, formato: FormatoReportes, directorio: &Path) -> Result<(), String> {
+        let mut salida = crear_salida(formato, directorio);
+        salida.iniciar(&self.metadatos)?;
+        self.salida = Some(salida);
+        self.reportes_escritos = 0;
+        self.eventos_escritos = 0;
+        self.piramide_escrita = 0;
+        self.bitacora_escrita = 0;
+        self.escribir_salida();
+        Ok(())
+    }
+
+    // Escribe y vacia las filas nuevas; ante un error se avisa una vez y se deja de escribir
+    fn escribir_salida(&mut self) {
+        let Some(salida) = self.salida.as_mut() else { return };
+        let resultado = (|| {
+            for r in &self.reportes[self.reportes_escritos..] {
+                salida.escribir_reporte(r)?;
+            }
+            for e in &self.planificador.registro()[self.eventos_escritos..] {
+                salida.escribir_evento(e)?;
+            }
+            for c in &self.piramide_edades[self.piramide_escrita..] {
+                salida.escribir_piramide(c)?;
+            }
+            for r in &self.bitacora_depredadores[self.bitacora_escrita..] {
+                salida.escribir_bitacora(r)?;
+            }
+            salida.vaciar()
+        })();
+        self.reportes_escritos = self.reportes.len();
+        self.eventos_escritos = self.planificador.registro().len();
+        self.piramide_escrita = self.piramide_edades.len();
+        self.bitacora_escrita = self.bitacora_depredadores.len();
+        if let Err(e) = resultado {
+            eprintln!("Error escribiendo reportes: {}", e);
+            self.salida = None;
+        }
+    }
+
+    /// Escribe lo pendiente y cierra la salida; se llama en cualquier forma de terminar
+    pub fn cerrar_salida(&mut self) {
+        self.escribir_salida();
+        if let Some(mut salida) = self.salida.take()
+            && let Err(e) = salida.finalizar()
+        {
+            eprintln!("Error cerrando reportes: {}", e);
+        }
+    }
+}
+
+impl Drop for Simulacion {
+    fn drop(&mut self) {
+        self.cerrar_salida();
+    }
+}
+
+// Indice espacial de las filas de presas (las que tienen Especie)
+fn indice_presas(mundo: &Mundo) -> IndiceEspacial {
+    let puntos = mundo.posiciones.iter().filter(|&(i, _)| mundo.especies.tiene(i)).map(|(i, &pos)| (i, pos));
+    IndiceEspacial::new(puntos, TAMANO_CELDA_INDICE)
+}
+
+// Mueve las filas con Posicion, Velocidad y Locomocion y descuenta todas las esperas. Los vivos que
+// salen del mundo emigran; lo que recorren los demas vivos se suma a su Bitacora, si la tienen
+fn actualizar_organismos(mundo: &mut Mundo, terreno: &Terreno, periodo: Periodo, dt: f32, paralelo: bool,
+                         contadores: &mut ContadoresDiarios) {
+    let filas: Vec<usize> = mundo.locomociones.filas()
+        .filter(|&i| mundo.posiciones.tiene(i) && mundo.velocidades.tiene(i))
+        .collect();
+    let lectura: &Mundo = mundo;
+    let movimientos = mapear(filas.len(), paralelo, |k| {
+        let i = filas[k];
+        let (mut pos, mut vel) = avanzar(lectura.posiciones[i], lectura.velocidades[i], &lectura.locomociones[i], terreno, periodo);
+        let emigro = lectura.esta_vivo(i) && aplicar_borde(&mut pos, &mut vel);
+        (pos, vel, emigro)
+    });
+    for (&i, (pos, vel, emigro)) in filas.iter().zip(movimientos) {
+        if emigro {
+            mundo.matar(i);
+            contadores.emigraciones += 1;
+        }
+        let antes = mundo.posiciones[i];
+        if mundo.vivos[i] && let Some(b) = mundo.bitacoras.get_mut(i) {
+            b.distancia_dia += distancia(antes.x, antes.y, pos.x, pos.y);
+        }
+        mundo.posiciones[i] = pos;
+        mundo.velocidades[i] = vel;
+    }
+    for (_, espera) in mundo.esperas.iter_mut() {
+        espera.avanzar(dt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sistemas::inicializacion::inicializar_depredadores;
+    use crate::sistemas::rendimiento::poblar_adultas;
+    use crate::entidades::Plantilla;
+    use crate::entidades::componentes::{Posicion, Cuerpo};
+    use crate::utilidades::renderizado::VistaOrganismo;
+
+    // Posicion, velocidad y edad de una presa
+    type EstadoPresa = (f32, f32, f32, f32, u32);
+
+    // Reportes del dia y estado de cada presa al final de una corrida
+    fn correr(semilla: u64, paralelo: bool) -> (Vec<String>, Vec<EstadoPresa>) {
+        let mut sim = Simulacion::new(semilla);
+        sim.paralelo = paralelo;
+        while sim.dias < 10 {
+            sim.paso(PASO_SIN_INTERFAZ);
+        }
+        let reportes = sim.reportes.iter().map(|r| serde_json::to_string(r).unwrap()).collect();
+        (reportes, estado_presas(&sim.mundo))
+    }
+
+    fn estado_presas(mundo: &Mundo) -> Vec<EstadoPresa> {
+        mundo.especies.filas()
+            .map(|i| {
+                let (pos, vel) = (mundo.posiciones[i], mundo.velocidades[i]);
+                (pos.x, pos.y, vel.vx, vel.vy, mundo.crecimientos[i].edad)
+            })
+            .collect()
+    }
+
+    // --semilla: toda la aleatoriedad (crias, depredacion, eventos) sale de sim.rng
+    #[test]
+    fn misma_semilla_misma_corrida() {
+        assert_eq!(correr(11, false), correr(11, false));
+        assert_ne!(correr(11, false), correr(12, false));
+    }
+
+    // Posicion, reserva, objetivo y vida de un depredador
+    type EstadoDepredador = (f32, f32, f32, Option<Entidad>, bool);
+
+    // Muchas presas adultas y un depredador de cada especie, para que el paso paralelo
+    // reparta trabajo real y todas las estrategias de caza intervengan
+    fn correr_poblada(paralelo: bool) -> (Vec<String>, Vec<EstadoPresa>, Vec<EstadoDepredador>) {
+        let mut sim = Simulacion::new(7);
+        sim.paralelo = paralelo;
+        poblar_adultas(&mut sim, 600);
+        sim.mundo.retener(|m, i| !m.cazas.tiene(i));
+        inicializar_depredadores(&mut sim.mundo, &EspecieDepredador::TODAS, EspecieDepredador::TODAS.len() * 2, &sim.terreno, &mut sim.rng);
+        while sim.dias < 3 {
+            sim.paso(PASO_SIN_INTERFAZ);
+        }
+        let reportes = sim.reportes.iter().map(|r| serde_json::to_string(r).unwrap()).collect();
+        let m = &sim.mundo;
+        let depredadores = m.cazas.iter()
+            .map(|(i, c)| (m.posiciones[i].x, m.posiciones[i].y, m.dietas[i].reserva, c.objetivo, m.esta_vivo(i)))
+            .collect();
+        (reportes, estado_presas(m), depredadores)
+    }
+
+    // Una combinacion nueva de componentes (una planta: solo Posicion y Cuerpo) no necesita
+    // codigo propio; ningun sistema la mueve, la caza ni la dibuja
+    #[test]
+    fn una_planta_pasa_por_los_sistemas_sin_cambios() {
+        let mut sim = Simulacion::new(3);
+        sim.planificador = Planificador::sin_aleatorios(Vec::new());
+        let planta = sim.mundo.crear(Plantilla {
+            posicion: Some(Posicion { x: 100.0, y: 100.0 }),
+            cuerpo: Some(Cuerpo { radio: 4.0 }),
+            ..Plantilla::default()
+        });
+        while sim.dias < 5 {
+            sim.paso(PASO_SIN_INTERFAZ);
+            assert!(sim.mundo.cazas.iter().all(|(_, c)| c.objetivo != Some(planta)));
+        }
+        let i = sim.mundo.fila(planta).unwrap();
+        assert!(sim.mundo.esta_vivo(i));
+        assert_eq!(sim.mundo.posiciones[i], Posicion { x: 100.0, y: 100.0 });
+        assert!(VistaOrganismo::de(&sim.mundo, i).is_none());
+    }
+
+    #[test]
+    fn paso_paralelo_igual_al_serial() {
+        let serial = correr_poblada(false);
+        let grupo = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let paralelo = grupo.install(|| correr_poblada(true));
+        assert!(serial.1.len() > 500);
+        assert_eq!(serial.2.len(), EspecieDepredador::TODAS.len() * 2);
+        assert_eq!(serial, paralelo);
+    }
+}
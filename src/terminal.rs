@@ -0,0 +1,197 @@
+use std::io;
+use std::time::Duration;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Sparkline, Table, Widget};
+
+use crate::entidades::{Especie, TipoCelda};
+use crate::sistemas::Simulacion;
+use crate::sistemas::estaciones::Estacion;
+use crate::utilidades::configuraciones::*;
+use crate::utilidades::reportes::EstadisticasEspecie;
+use crate::utilidades::renderizado::{Rgba, color_especie, color_celda};
+
+// ==================== TERMINAL ====================
+// Interfaz de texto para maquinas sin pantalla: el mundo como rejilla de caracteres, series de
+// poblacion y la tabla de depredadores. Espacio pausa, +/- cambia la velocidad, q o Esc sale
+pub fn ejecutar(sim: &mut Simulacion) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let resultado = bucle(&mut terminal, sim);
+    ratatui::restore();
+    resultado.map_err(|e| e.to_string())
+}
+
+fn bucle(terminal: &mut DefaultTerminal, sim: &mut Simulacion) -> io::Result<()> {
+    let mut pausa = false;
+    let mut velocidad = 0;
+    loop {
+        terminal.draw(|f| dibujar(f, sim, pausa, VELOCIDADES_TERMINAL[velocidad]))?;
+        let mut un_paso = false;
+        if event::poll(Duration::from_millis(CUADRO_TERMINAL_MS))?
+            && let Event::Key(tecla) = event::read()?
+            && tecla.kind == KeyEventKind::Press
+        {
+            match tecla.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if tecla.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                KeyCode::Char(' ') => pausa = !pausa,
+                KeyCode::Char('+') => velocidad = (velocidad + 1).min(VELOCIDADES_TERMINAL.len() - 1),
+                KeyCode::Char('-') => velocidad = velocidad.saturating_sub(1),
+                KeyCode::Char('.') => un_paso = true,
+                _ => {}
+            }
+        }
+        let pasos = if pausa { un_paso as u32 } else { VELOCIDADES_TERMINAL[velocidad] };
+        for _ in 0..pasos {
+            sim.paso(PASO_SIN_INTERFAZ);
+        }
+    }
+}
+
+fn color(c: Rgba) -> Color {
+    Color::Rgb(c.r, c.g, c.b)
+}
+
+// ==================== DIBUJO ====================
+fn dibujar(f: &mut Frame, sim: &Simulacion, pausa: bool, pasos_por_cuadro: u32) {
+    let filas_tabla = sim.mundo.cazas.filas().count().min(8) as u16 + 3;
+    let [cabecera, centro, tabla] = Layout::vertical([
+        Constraint::Length(7),
+        Constraint::Min(8),
+        Constraint::Length(filas_tabla),
+    ]).areas(f.area());
+    let [mundo, series] = Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)]).areas(centro);
+
+    dibujar_cabecera(f, cabecera, sim, pausa, pasos_por_cuadro);
+    let bloque = Block::bordered().title(" Mundo ");
+    let interior = bloque.inner(mundo);
+    f.render_widget(bloque, mundo);
+    f.render_widget(RejillaMundo { sim }, interior);
+    dibujar_series(f, series, sim);
+    dibujar_tabla(f, tabla, sim);
+}
+
+// Dia, velocidad y el resumen por especie de dibujar_ui
+fn dibujar_cabecera(f: &mut Frame, area: Rect, sim: &Simulacion, pausa: bool, pasos_por_cuadro: u32) {
+    let estado = if pausa { "PAUSA".to_string() } else { format!("{} pasos/cuadro", pasos_por_cuadro) };
+    let mut lineas = vec![Line::from(format!("Día: {} ({}, {}) | {}",
+        sim.dias, Estacion::del_dia(sim.dias).nombre(), sim.periodo().nombre(), estado)).bold()];
+    for (especie, plural) in Especie::TODAS.into_iter().zip(["Conejos", "Ratones", "Ardillas"]) {
+        let e = EstadisticasEspecie::de(&sim.mundo, especie);
+        lineas.push(Line::from(format!("{}: {} (edad promedio: {:.1}, peso promedio: {:.1})",
+            plural, e.conteo, e.edad_media, e.peso_medio)).fg(color(color_especie(especie))));
+    }
+    lineas.push(Line::from("Espacio: pausa | +/-: velocidad | .: un paso (en pausa) | q/Esc: salir").dark_gray());
+    f.render_widget(Paragraph::new(lineas).block(Block::bordered().title(" Presa-Depredador ")), area);
+}
+
+// Una serie por especie y otra de depredadores vivos, con los ultimos dias que caben
+fn dibujar_series(f: &mut Frame, area: Rect, sim: &Simulacion) {
+    let areas = Layout::vertical([Constraint::Ratio(1, 4); 4]).split(area);
+    let ancho = area.width.saturating_sub(2) as usize;
+    let desde = sim.reportes.len().saturating_sub(ancho);
+    let recientes = &sim.reportes[desde..];
+    let series: [(&str, Color, Vec<u64>); 4] = [
+        ("Conejos", color(color_especie(Especie::Conejo)), recientes.iter().map(|r| r.conteo_conejos as u64).collect()),
+        ("Ratones", color(color_especie(Especie::Raton)), recientes.iter().map(|r| r.conteo_ratones as u64).collect()),
+        ("Ardillas", color(color_especie(Especie::Ardilla)), recientes.iter().map(|r| r.conteo_ardillas as u64).collect()),
+        ("Depredadores", Color::Red, recientes.iter().map(|r| r.depredadores_vivos as u64).collect()),
+    ];
+    for ((nombre, tono, datos), &zona) in series.iter().zip(areas.iter()) {
+        let titulo = format!(" {} ({}) ", nombre, datos.last().copied().unwrap_or(0));
+        let grafico = Sparkline::default()
+            .block(Block::bordered().title(titulo))
+            .data(datos)
+            .style(Style::default().fg(*tono));
+        f.render_widget(grafico, zona);
+    }
+}
+
+// La tabla de depredadores de dibujar_ui
+fn dibujar_tabla(f: &mut Frame, area: Rect, sim: &Simulacion) {
+    let m = &sim.mundo;
+    let filas = m.cazas.iter().enumerate().map(|(k, (i, caza))| {
+        let estado = if m.esta_vivo(i) { m.salud[i].estado.nombre() } else { "Muerto" };
+        Row::new(vec![
+            format!("{}", k + 1),
+            m.especies_depredador[i].nombre().to_string(),
+            caza.estrategia.nombre().to_string(),
+            format!("{:.1}", m.dietas[i].reserva),
+            estado.to_string(),
+        ])
+    });
+    let anchos = [Constraint::Length(4), Constraint::Length(8), Constraint::Length(16), Constraint::Length(8), Constraint::Length(8)];
+    let tabla = Table::new(filas, anchos)
+        .header(Row::new(vec!["#", "Especie", "Estrategia", "Reserva", "Estado"]).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(Color::Red))
+        .block(Block::bordered().title(" Depredadores "));
+    f.render_widget(tabla, area);
+}
+
+// ==================== REJILLA DEL MUNDO ====================
+// Cada caracter cubre un rectangulo del mundo: un depredador tapa a las presas, y entre las
+// presas gana la especie mas numerosa (mayuscula si hay varias); si no hay nadie, el terreno
+struct RejillaMundo<'a> {
+    sim: &'a Simulacion,
+}
+
+impl Widget for RejillaMundo<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let (columnas, filas) = (area.width as usize, area.height as usize);
+        let (ancho, alto) = (ANCHO_MUNDO / columnas as f32, ALTO_MUNDO / filas as f32);
+        let celda = |x: f32, y: f32| {
+            let c = ((x / ancho).max(0.0) as usize).min(columnas - 1);
+            let f = ((y / alto).max(0.0) as usize).min(filas - 1);
+            f * columnas + c
+        };
+        let mut presas = vec![[0u32; 3]; columnas * filas];
+        let m = &self.sim.mundo;
+        for (i, especie) in m.especies.iter().filter(|&(i, _)| m.esta_vivo(i)) {
+            let p = m.posiciones[i];
+            presas[celda(p.x, p.y)][especie.indice()] += 1;
+        }
+        let mut depredadores = vec![None; columnas * filas];
+        for (i, &especie) in m.especies_depredador.iter().filter(|&(i, _)| m.esta_vivo(i)) {
+            let d = m.posiciones[i];
+            depredadores[celda(d.x, d.y)] = Some(especie);
+        }
+
+        for f in 0..filas {
+            for c in 0..columnas {
+                let i = f * columnas + c;
+                let (simbolo, tono) = if let Some(especie) = depredadores[i] {
+                    (inicial(especie.nombre()).to_ascii_uppercase(), Color::Red)
+                } else if let Some((k, &n)) = presas[i].iter().enumerate().filter(|(_, n)| **n > 0).max_by_key(|(_, n)| **n) {
+                    let especie = Especie::TODAS[k];
+                    let letra = inicial(especie.nombre());
+                    (if n > 1 { letra.to_ascii_uppercase() } else { letra }, color(color_especie(especie)))
+                } else {
+                    let tipo = self.sim.terreno.celda_en((c as f32 + 0.5) * ancho, (f as f32 + 0.5) * alto);
+                    let simbolo = match tipo {
+                        TipoCelda::Campo => ' ',
+                        TipoCelda::Bosque => '^',
+                        TipoCelda::Agua => '~',
+                        TipoCelda::Rocas => '#',
+                        TipoCelda::Madriguera => 'o',
+                    };
+                    (simbolo, color(color_celda(tipo)))
+                };
+                if let Some(cell) = buf.cell_mut((area.x + c as u16, area.y + f as u16)) {
+                    cell.set_char(simbolo).set_fg(tono);
+                }
+            }
+        }
+    }
+}
+
+fn inicial(nombre: &str) -> char {
+    nombre.chars().next().unwrap_or('?').to_ascii_lowercase()
+}
@@ -0,0 +1,132 @@
+use macroquad::prelude::*;
+use crate::entidades::Mundo;
+use crate::utilidades::configuraciones::{ANCHO_MUNDO, ALTO_MUNDO};
+use crate::utilidades::renderizado::color_especie;
+use crate::ui::renderizado::color;
+
+const ZOOM_MIN: f32 = 0.1;
+const ZOOM_MAX: f32 = 10.0;
+const ANCHO_MINIMAPA: f32 = 200.0;
+
+// ==================== CAMARA ====================
+pub struct Camara {
+    centro: Vec2,
+    zoom: f32,
+    seguir: bool,
+    arrastre: Option<Vec2>,
+}
+
+impl Camara {
+    // Arranca mostrando el mundo completo
+    pub fn new() -> Self {
+        Self {
+            centro: vec2(ANCHO_MUNDO / 2.0, ALTO_MUNDO / 2.0),
+            zoom: (screen_width() / ANCHO_MUNDO).min(screen_height() / ALTO_MUNDO),
+            seguir: false,
+            arrastre: None,
+        }
+    }
+
+    fn camera2d(&self) -> Camera2D {
+        Camera2D {
+            target: self.centro,
+            zoom: vec2(self.zoom * 2.0 / screen_width(), self.zoom * 2.0 / screen_height()),
+            ..Default::default()
+        }
+    }
+
+    /// Convierte coordenadas de pantalla (p. ej. el mouse) a coordenadas del mundo
+    pub fn pantalla_a_mundo(&self, x: f32, y: f32) -> (f32, f32) {
+        let p = self.camera2d().screen_to_world(vec2(x, y));
+        (p.x, p.y)
+    }
+
+    // Rueda: zoom hacia el cursor, click derecho: arrastrar, C: seguir al seleccionado
+    pub fn manejar_entrada(&mut self, objetivo: Option<(f32, f32)>) {
+        let (_, rueda) = mouse_wheel();
+        if rueda != 0.0 {
+            let (mx, my) = mouse_position();
+            let antes = self.camera2d().screen_to_world(vec2(mx, my));
+            let factor = if rueda > 0.0 { 1.1 } else { 1.0 / 1.1 };
+            self.zoom = (self.zoom * factor).clamp(ZOOM_MIN, ZOOM_MAX);
+            let despues = self.camera2d().screen_to_world(vec2(mx, my));
+            self.centro += antes - despues;
+        }
+
+        if is_mouse_button_down(MouseButton::Right) {
+            let (mx, my) = mouse_position();
+            let actual = vec2(mx, my);
+            if let Some(previo) = self.arrastre {
+                self.centro -= (actual - previo) / self.zoom;
+                self.seguir = false;
+            }
+            self.arrastre = Some(actual);
+        } else {
+            self.arrastre = None;
+        }
+
+        if is_key_pressed(KeyCode::C) {
+            self.seguir = !self.seguir;
+        }
+        if let (true, Some((x, y))) = (self.seguir, objetivo) {
+            self.centro = vec2(x, y);
+        }
+
+        self.centro.x = self.centro.x.clamp(0.0, ANCHO_MUNDO);
+        self.centro.y = self.centro.y.clamp(0.0, ALTO_MUNDO);
+    }
+
+    /// Activa la camara para dibujar el mundo; volver con `set_default_camera` para la interfaz
+    pub fn activar(&self) {
+        set_camera(&self.camera2d());
+        draw_rectangle_lines(0.0, 0.0, ANCHO_MUNDO, ALTO_MUNDO, 2.0 / self.zoom, DARKGRAY);
+    }
+
+    // Click izquierdo sobre el minimapa: centra la camara en ese punto
+    pub fn click_en_minimapa(&mut self, mx: f32, my: f32) -> bool {
+        let (x0, y0, escala) = Self::rect_minimapa();
+        let x = (mx - x0) / escala;
+        let y = (my - y0) / escala;
+        if (0.0..=ANCHO_MUNDO).contains(&x) && (0.0..=ALTO_MUNDO).contains(&y) {
+            self.centro = vec2(x, y);
+            self.seguir = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn rect_minimapa() -> (f32, f32, f32) {
+        let escala = ANCHO_MINIMAPA / ANCHO_MUNDO;
+        (10.0, screen_height() - ALTO_MUNDO * escala - 10.0, escala)
+    }
+
+    /// Minimapa en coordenadas de pantalla con los organismos y el area visible
+    pub fn dibujar_minimapa(&self, mundo: &Mundo) {
+        let (x0, y0, escala) = Self::rect_minimapa();
+        draw_rectangle(x0, y0, ANCHO_MUNDO * escala, ALTO_MUNDO * escala, Color::new(1.0, 1.0, 1.0, 0.85));
+        draw_rectangle_lines(x0, y0, ANCHO_MUNDO * escala, ALTO_MUNDO * escala, 1.0, BLACK);
+
+        for (i, &especie) in mundo.especies.iter().filter(|&(i, _)| mundo.esta_vivo(i)) {
+            let p = mundo.posiciones[i];
+            draw_circle(x0 + p.x * escala, y0 + p.y * escala, 1.5, color(color_especie(especie)));
+        }
+        for i in mundo.especies_depredador.filas().filter(|&i| mundo.esta_vivo(i)) {
+            let d = mundo.posiciones[i];
+            draw_circle(x0 + d.x * escala, y0 + d.y * escala, 2.5, RED);
+        }
+
+        // Rectangulo del area visible, recortado al mundo
+        let (ax, ay) = self.pantalla_a_mundo(0.0, 0.0);
+        let (bx, by) = self.pantalla_a_mundo(screen_width(), screen_height());
+        let ax = ax.clamp(0.0, ANCHO_MUNDO);
+        let ay = ay.clamp(0.0, ALTO_MUNDO);
+        let bx = bx.clamp(0.0, ANCHO_MUNDO);
+        let by = by.clamp(0.0, ALTO_MUNDO);
+        draw_rectangle_lines(x0 + ax * escala, y0 + ay * escala, (bx - ax) * escala, (by - ay) * escala, 1.0, MAGENTA);
+
+        if self.seguir {
+            draw_text("Siguiendo", x0, y0 - 4.0, 14.0, MAGENTA);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use macroquad::prelude::*;
+use crate::utilidades::densidad::{CapaDensidad, MapaDensidad};
+
+// ==================== PANEL MAPA DE DENSIDAD ====================
+pub struct PanelDensidad {
+    pub visible: bool,
+    pub capa: CapaDensidad,
+}
+
+impl PanelDensidad {
+    pub fn new() -> Self {
+        Self { visible: false, capa: CapaDensidad::Conejos }
+    }
+
+    // M: mostrar/ocultar, N: cambiar de capa
+    pub fn manejar_teclas(&mut self) {
+        if is_key_pressed(KeyCode::M) {
+            self.visible = !self.visible;
+        }
+        if is_key_pressed(KeyCode::N) {
+            self.capa = self.capa.siguiente();
+        }
+    }
+
+    /// Celdas coloreadas de amarillo a rojo segun la densidad; se dibuja con la camara del mundo activa
+    pub fn dibujar_mapa(&self, mapa: &MapaDensidad) {
+        if !self.visible {
+            return;
+        }
+        let valores = mapa.acumulada(self.capa);
+        let maximo = valores.iter().cloned().fold(0.0, f32::max);
+        if maximo <= 0.0 {
+            return;
+        }
+        let (ancho, alto) = mapa.tamano_celda();
+        for (i, &v) in valores.iter().enumerate().filter(|(_, v)| **v > 0.0) {
+            let t = v / maximo;
+            let (c, f) = (i % mapa.columnas, i / mapa.columnas);
+            draw_rectangle(c as f32 * ancho, f as f32 * alto, ancho, alto, Color::new(1.0, 1.0 - t, 0.0, 0.2 + 0.5 * t));
+        }
+    }
+
+    /// Capa, ventana de dias y maximo por celda, arriba a la derecha
+    pub fn dibujar_leyenda(&self, mapa: &MapaDensidad) {
+        if !self.visible {
+            return;
+        }
+        let maximo = mapa.acumulada(self.capa).iter().cloned().fold(0.0, f32::max);
+        draw_text(
+            &format!("Densidad: {} (ultimos {} dias, max {:.0} por celda)", self.capa.nombre(), mapa.dias_ventana, maximo),
+            screen_width() - 380.0,
+            20.0,
+            18.0,
+            MAROON,
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use macroquad::prelude::*;
+use crate::utilidades::configuraciones::{FORMATO_GRABACION, CUADROS_POR_CAPTURA};
+use crate::utilidades::grabacion::{Grabadora, Lienzo};
+
+// ==================== PANEL GRABACION ====================
+pub struct PanelGrabacion {
+    grabadora: Option<Grabadora>,
+}
+
+impl PanelGrabacion {
+    pub fn new() -> Self {
+        Self { grabadora: None }
+    }
+
+    // R: empezar/terminar de grabar
+    pub fn manejar_teclas(&mut self) {
+        if is_key_pressed(KeyCode::R) {
+            if self.grabadora.is_some() {
+                self.finalizar();
+            } else {
+                self.grabadora = Some(Grabadora::new(FORMATO_GRABACION, CUADROS_POR_CAPTURA));
+            }
+        }
+    }
+
+    /// Captura la pantalla ya dibujada si toca este cuadro; se llama antes de dibujar_indicador
+    pub fn capturar(&mut self) {
+        let Some(g) = self.grabadora.as_mut() else { return };
+        if g.toca_capturar()
+            && let Err(e) = g.agregar(&lienzo_de_pantalla(&get_screen_data()))
+        {
+            eprintln!("Error grabando: {}", e);
+            self.grabadora = None;
+        }
+    }
+
+    // Se dibuja despues de capturar para que no salga en la grabacion
+    pub fn dibujar_indicador(&self) {
+        if let Some(g) = &self.grabadora {
+            draw_circle(screen_width() - 20.0, 40.0, 6.0, RED);
+            draw_text(&format!("REC {}", g.guardados()), screen_width() - 90.0, 45.0, 18.0, RED);
+        }
+    }
+
+    /// Termina la grabacion en curso (tambien al salir)
+    pub fn finalizar(&mut self) {
+        if let Some(mut g) = self.grabadora.take() {
+            g.finalizar();
+            println!("Grabacion: {} cuadros en {}", g.guardados(), g.formato().ruta());
+        }
+    }
+}
+
+// La captura de pantalla de macroquad viene de abajo hacia arriba; el lienzo va de arriba hacia abajo
+fn lienzo_de_pantalla(imagen: &Image) -> Lienzo {
+    let (ancho, alto) = (imagen.width as usize, imagen.height as usize);
+    let pixeles = imagen.bytes.chunks(ancho * 4).rev().flatten().copied().collect();
+    Lienzo { ancho, alto, pixeles }
+}
@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+use macroquad::prelude::*;
+use crate::entidades::{Mundo, Entidad};
+use crate::utilidades::configuraciones::{ANCHO_MUNDO, ALTO_MUNDO};
+
+const LARGO_RASTRO: usize = 180;
+const ANCHO_PANEL: f32 = 260.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Seleccion { Presa(Entidad), Depredador(Entidad) }
+
+// ==================== INSPECTOR DE ORGANISMOS ====================
+pub struct Inspector {
+    seleccion: Option<Seleccion>,
+    rastro: VecDeque<(f32, f32)>,
+}
+
+impl Inspector {
+    pub fn new() -> Self {
+        Self { seleccion: None, rastro: VecDeque::new() }
+    }
+
+    /// Posicion actual del organismo seleccionado, si sigue vivo
+    pub fn posicion(&self, mundo: &Mundo) -> Option<(f32, f32)> {
+        let pos = mundo.posiciones[self.fila(mundo)?];
+        Some((pos.x, pos.y))
+    }
+
+    // Fila del organismo seleccionado, si sigue vivo
+    fn fila(&self, mundo: &Mundo) -> Option<usize> {
+        let (Seleccion::Presa(id) | Seleccion::Depredador(id)) = self.seleccion?;
+        mundo.fila(id).filter(|&i| mundo.esta_vivo(i))
+    }
+
+    // Click izquierdo: selecciona el organismo bajo el cursor (o limpia si no hay ninguno)
+    pub fn manejar_click(&mut self, mx: f32, my: f32, mundo: &Mundo) {
+        let dentro = |&i: &usize| {
+            let (pos, r) = (mundo.posiciones[i], mundo.cuerpos[i].radio);
+            let dx = pos.x - mx;
+            let dy = pos.y - my;
+            mundo.esta_vivo(i) && dx * dx + dy * dy <= r * r
+        };
+
+        // Los depredadores se dibujan encima, por eso tienen prioridad
+        let nueva = mundo.especies_depredador.filas()
+            .find(dentro)
+            .map(|i| Seleccion::Depredador(mundo.id(i)))
+            .or_else(|| mundo.especies.filas()
+                .find(dentro)
+                .map(|i| Seleccion::Presa(mundo.id(i))));
+
+        if nueva != self.seleccion {
+            self.rastro.clear();
+        }
+        self.seleccion = nueva;
+    }
+
+    // Guarda la posicion del frame en el rastro
+    pub fn actualizar(&mut self, mundo: &Mundo) {
+        if let Some(pos) = self.posicion(mundo) {
+            self.rastro.push_back(pos);
+            if self.rastro.len() > LARGO_RASTRO {
+                self.rastro.pop_front();
+            }
+        }
+    }
+
+    /// Resalta el organismo y dibuja su rastro, en coordenadas del mundo
+    pub fn dibujar_resaltado(&self, mundo: &Mundo) {
+        let n = self.rastro.len();
+        for (i, (a, b)) in self.rastro.iter().zip(self.rastro.iter().skip(1)).enumerate() {
+            // En modo toroidal no unir los puntos donde el organismo cruzo el borde
+            if (a.0 - b.0).abs() > ANCHO_MUNDO / 2.0 || (a.1 - b.1).abs() > ALTO_MUNDO / 2.0 {
+                continue;
+            }
+            let alfa = (i + 1) as f32 / n as f32;
+            draw_line(a.0, a.1, b.0, b.1, 2.0, Color::new(0.1, 0.1, 0.1, alfa));
+        }
+
+        if let Some(i) = self.fila(mundo) {
+            let (pos, r) = (mundo.posiciones[i], mundo.cuerpos[i].radio);
+            draw_circle_lines(pos.x, pos.y, r + 6.0, 2.0, MAGENTA);
+        }
+    }
+
+    /// Panel con el estado completo del organismo seleccionado, en coordenadas de pantalla
+    pub fn dibujar_panel(&self, mundo: &Mundo) {
+        let Some(seleccion) = self.seleccion else { return; };
+
+        let lineas: Vec<String> = match (seleccion, self.fila(mundo)) {
+            (Seleccion::Presa(id), Some(i)) => {
+                let (crecimiento, salud, reproduccion) = (mundo.crecimientos[i], mundo.salud[i], mundo.reproducciones[i]);
+                vec![
+                    format!("Presa #{}", id),
+                    format!("Especie: {}", mundo.especies[i].nombre()),
+                    format!("Sexo: {}", reproduccion.sexo.nombre()),
+                    format!("Edad: {} días", crecimiento.edad),
+                    format!("Peso: {:.2}", crecimiento.peso),
+                    format!("Salud: {}", salud.estado.nombre()),
+                    format!("Días enfermo: {}", salud.dias_enfermo),
+                    format!("Inmune: {} días", salud.dias_inmune),
+                    format!("Cooldown: {:.2}", mundo.esperas[i].restante.max(0.0)),
+                    format!("Modo reproducción: {}", if reproduccion.activa { "Sí" } else { "No" }),
+                    format!("Objetivo: {}", reproduccion.pareja.map_or("ninguno".to_string(), |o| format!("pareja #{}", o))),
+                ]
+            }
+            (Seleccion::Presa(id), None) => vec![format!("Presa #{}", id), "Muerta".to_string()],
+            (Seleccion::Depredador(id), Some(i)) => {
+                let (caza, salud) = (&mundo.cazas[i], mundo.salud[i]);
+                vec![
+                    format!("Depredador #{}", id),
+                    format!("Especie: {}", mundo.especies_depredador[i].nombre()),
+                    format!("Estrategia: {}", caza.estrategia.nombre()),
+                    format!("Reserva: {:.2}", mundo.dietas[i].reserva),
+                    format!("Salud: {}", salud.estado.nombre()),
+                    format!("Días enfermo: {}", salud.dias_enfermo),
+                    format!("Cooldown: {:.2}", mundo.esperas[i].restante),
+                    format!("Objetivo: {}", caza.objetivo.map_or("ninguno".to_string(), |o| format!("presa #{}", o))),
+                ]
+            }
+            (Seleccion::Depredador(id), None) => vec![format!("Depredador #{}", id), "Muerto".to_string()],
+        };
+
+        let x0 = screen_width() - ANCHO_PANEL - 10.0;
+        let y0 = 10.0;
+        let alto = 10.0 + lineas.len() as f32 * 20.0;
+        draw_rectangle(x0, y0, ANCHO_PANEL, alto, Color::new(1.0, 1.0, 1.0, 0.85));
+        draw_rectangle_lines(x0, y0, ANCHO_PANEL, alto, 1.0, MAGENTA);
+        for (i, linea) in lineas.iter().enumerate() {
+            draw_text(linea, x0 + 8.0, y0 + 22.0 + i as f32 * 20.0, 18.0, BLACK);
+        }
+    }
+}
+
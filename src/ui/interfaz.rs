@@ -1,111 +1,84 @@
 use macroquad::prelude::*;
-use crate::entidades::{Presa,Depredador,Especie};
-use crate::utilidades::csv::{EstadisticasDiarias,guardar_reportes_csv};
+use crate::entidades::{Mundo, Especie};
+use crate::sistemas::estaciones::Estacion;
+use crate::sistemas::ciclo_diario::Periodo;
+use crate::utilidades::reportes::EstadisticasEspecie;
+use crate::utilidades::renderizado::color_especie;
+use crate::ui::renderizado::color;
 
 pub fn dibujar_ui(
     dias: u32,
-    presas: &Vec<Presa>,
-    depredadores: &Vec<Depredador>,
-    reportes: &Vec<EstadisticasDiarias>,
+    periodo: Periodo,
+    mundo: &Mundo,
 ) {
-    let mut conteo = [0, 0, 0];
-    let mut suma_edades = [0u32, 0, 0];
-    let mut suma_pesos = [0.0f32, 0.0, 0.0];
-
-    // === Recolección de datos ===
-    for p in presas {
-        match p.especie() {
-            Especie::Conejo => {
-                conteo[0] += 1;
-                suma_edades[0] += p.edad();
-                suma_pesos[0] += p.peso();
-            }
-            Especie::Raton => {
-                conteo[1] += 1;
-                suma_edades[1] += p.edad();
-                suma_pesos[1] += p.peso();
-            }
-            Especie::Ardilla => {
-                conteo[2] += 1;
-                suma_edades[2] += p.edad();
-                suma_pesos[2] += p.peso();
-            }
-        }
-    }
-
-    // === Funciones locales para promedios ===
-    let promedio = |suma: u32, count: i32| -> f32 {
-        if count > 0 {
-            suma as f32 / count as f32
-        } else {
-            0.0
-        }
-    };
-    let promedio_peso = |suma: f32, count: i32| -> f32 {
-        if count > 0 {
-            suma / count as f32
-        } else {
-            0.0
-        }
-    };
+    let [conejos, ratones, ardillas] = Especie::TODAS.map(|e| EstadisticasEspecie::de(mundo, e));
 
     // === Texto de cabecera ===
     draw_text(
-        &format!("Día: {} | Esc: para finalizar y generar reporte", dias),
+        &format!("Día: {} ({}, {}) | Esc: para finalizar y generar reporte", dias, Estacion::del_dia(dias).nombre(), periodo.nombre()),
         10.0,
         20.0,
         20.0,
         BLACK,
     );
+    draw_text(
+        "F/X/Y/G: plano de fase | T: serie temporal | M/N: densidad | R: grabar | Click: inspeccionar | Rueda/Click der.: cámara | C: seguir",
+        10.0,
+        36.0,
+        14.0,
+        DARKGRAY,
+    );
 
     // === Información por especie ===
     draw_text(
         &format!(
             "Conejos: {} (edad promedio: {:.1}, peso promedio: {:.1})",
-            conteo[0],
-            promedio(suma_edades[0], conteo[0]),
-            promedio_peso(suma_pesos[0], conteo[0])
+            conejos.conteo,
+            conejos.edad_media,
+            conejos.peso_medio
         ),
         10.0,
         50.0,
         20.0,
-        Especie::Conejo.color(),
+        color(color_especie(Especie::Conejo)),
     );
 
     draw_text(
         &format!(
             "Ratones: {} (edad promedio: {:.1}, peso promedio: {:.1})",
-            conteo[1],
-            promedio(suma_edades[1], conteo[1]),
-            promedio_peso(suma_pesos[1], conteo[1])
+            ratones.conteo,
+            ratones.edad_media,
+            ratones.peso_medio
         ),
         10.0,
         70.0,
         20.0,
-        Especie::Raton.color(),
+        color(color_especie(Especie::Raton)),
     );
 
     draw_text(
         &format!(
             "Ardillas: {} (edad promedio: {:.1}, peso promedio: {:.1})",
-            conteo[2],
-            promedio(suma_edades[2], conteo[2]),
-            promedio_peso(suma_pesos[2], conteo[2])
+            ardillas.conteo,
+            ardillas.edad_media,
+            ardillas.peso_medio
         ),
         10.0,
         90.0,
         20.0,
-        Especie::Ardilla.color(),
+        color(color_especie(Especie::Ardilla)),
     );
 
     // === Información de depredadores ===
-    for (i, d) in depredadores.iter().enumerate() {
+    for (i, (d, caza)) in mundo.cazas.iter().enumerate() {
         draw_text(
             &format!(
-                "Depredador {} peso: {:.1} estado: {}",
+                "Depredador {} ({}, {}) peso: {:.1} estado: {}",
                 i + 1,
-                d.reserva(),
-                d.salud().nombre()
+                mundo.especies_depredador[d].nombre(),
+                caza.estrategia.nombre(),
+                mundo.dietas[d].reserva,
+                mundo.salud[d].estado.nombre()
             ),
             10.0,
             130.0 + i as f32 * 20.0,
@@ -113,14 +86,4 @@ pub fn dibujar_ui(
             RED,
         );
     }
-
-    // === Guardar reporte con ESC ===
-    if is_key_pressed(KeyCode::Escape) {
-        if let Err(e) = guardar_reportes_csv(reportes, "reportes.csv") {
-            eprintln!("Error guardando CSV: {}", e);
-        } else {
-            println!("Reportes guardados en reportes.csv");
-        }
-        std::process::exit(0); // cerrar limpio
-    }
 }
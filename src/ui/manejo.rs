@@ -0,0 +1,56 @@
+use macroquad::prelude::*;
+use crate::entidades::{Especie, EspecieDepredador};
+use crate::sistemas::intervenciones::Intervencion;
+use crate::utilidades::configuraciones::*;
+use crate::utilidades::renderizado::color_especie;
+use crate::ui::renderizado::color;
+
+// ==================== TECLAS DE MANEJO ====================
+pub struct PanelManejo {
+    especie: Especie,
+    especie_depredador: EspecieDepredador,
+}
+
+impl PanelManejo {
+    pub fn new() -> Self {
+        Self { especie: Especie::Conejo, especie_depredador: EspecieDepredador::Zorro }
+    }
+
+    // 1/2/3: especie, 4: especie de depredador, K: sacrificar, P: liberar presas, D: liberar depredador, V: vacunar, H: tratar
+    // Las liberaciones se hacen en la posicion del mouse (coordenadas del mundo)
+    pub fn intervencion_pedida(&mut self, mouse_mundo: (f32, f32)) -> Option<Intervencion> {
+        if is_key_pressed(KeyCode::Key1) { self.especie = Especie::Conejo; }
+        if is_key_pressed(KeyCode::Key2) { self.especie = Especie::Raton; }
+        if is_key_pressed(KeyCode::Key3) { self.especie = Especie::Ardilla; }
+        if is_key_pressed(KeyCode::Key4) { self.especie_depredador = self.especie_depredador.siguiente(); }
+
+        let (x, y) = mouse_mundo;
+        if is_key_pressed(KeyCode::K) {
+            Some(Intervencion::Sacrificio { especie: self.especie, cantidad: CANTIDAD_SACRIFICIO_TECLA })
+        } else if is_key_pressed(KeyCode::P) {
+            Some(Intervencion::LiberarPresas { especie: self.especie, cantidad: CANTIDAD_LIBERACION_TECLA, x, y })
+        } else if is_key_pressed(KeyCode::D) {
+            Some(Intervencion::LiberarDepredadores { especie: self.especie_depredador, cantidad: 1, x, y })
+        } else if is_key_pressed(KeyCode::V) {
+            Some(Intervencion::Vacunar { especie: self.especie, fraccion: FRACCION_VACUNACION_TECLA, dias: DIAS_INMUNIDAD_VACUNA })
+        } else if is_key_pressed(KeyCode::H) {
+            Some(Intervencion::TratarDepredadores)
+        } else {
+            None
+        }
+    }
+
+    pub fn dibujar(&self) {
+        draw_text(
+            &format!(
+                "Manejo [1/2/3]: {} [4]: {} | K: sacrificar | P: liberar presas | D: liberar depredador | V: vacunar | H: tratar",
+                self.especie.nombre(),
+                self.especie_depredador.nombre()
+            ),
+            10.0,
+            112.0,
+            14.0,
+            color(color_especie(self.especie)),
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use macroquad::prelude::*;
+use crate::utilidades::csv::EstadisticasDiarias;
+use crate::utilidades::plano_fase::{EjePresas, EjeDepredadores, puntos_plano_fase, limites_plano_fase, guardar_plano_fase_svg};
+
+const ANCHO_PANEL: f32 = 300.0;
+const ALTO_PANEL: f32 = 220.0;
+const MARGEN_PANEL: f32 = 30.0;
+
+// ==================== PANEL PLANO DE FASE ====================
+pub struct PanelFase {
+    pub visible: bool,
+    pub eje_x: EjePresas,
+    pub eje_y: EjeDepredadores,
+}
+
+impl PanelFase {
+    pub fn new() -> Self {
+        Self {
+            visible: true,
+            eje_x: EjePresas::Total,
+            eje_y: EjeDepredadores::Conteo,
+        }
+    }
+
+    // F: mostrar/ocultar, X: cambiar eje de presas, Y: cambiar eje de depredadores, G: exportar SVG
+    pub fn manejar_teclas(&mut self, reportes: &[EstadisticasDiarias]) {
+        if is_key_pressed(KeyCode::F) {
+            self.visible = !self.visible;
+        }
+        if is_key_pressed(KeyCode::X) {
+            self.eje_x = self.eje_x.siguiente();
+        }
+        if is_key_pressed(KeyCode::Y) {
+            self.eje_y = self.eje_y.siguiente();
+        }
+        if is_key_pressed(KeyCode::G) {
+            if let Err(e) = guardar_plano_fase_svg(reportes, self.eje_x, self.eje_y, "plano_fase.svg") {
+                eprintln!("Error guardando SVG: {}", e);
+            } else {
+                println!("Plano de fase guardado en plano_fase.svg");
+            }
+        }
+    }
+
+    pub fn dibujar(&self, reportes: &[EstadisticasDiarias]) {
+        if !self.visible {
+            return;
+        }
+
+        let x0 = screen_width() - ANCHO_PANEL - 10.0;
+        let y0 = screen_height() - ALTO_PANEL - 10.0;
+        draw_rectangle(x0, y0, ANCHO_PANEL, ALTO_PANEL, Color::new(1.0, 1.0, 1.0, 0.85));
+        draw_rectangle_lines(x0, y0, ANCHO_PANEL, ALTO_PANEL, 1.0, BLACK);
+
+        // Area util del grafico
+        let gx = x0 + MARGEN_PANEL;
+        let gy = y0 + 10.0;
+        let gw = ANCHO_PANEL - MARGEN_PANEL - 10.0;
+        let gh = ALTO_PANEL - MARGEN_PANEL - 10.0;
+        draw_line(gx, gy, gx, gy + gh, 1.0, BLACK);
+        draw_line(gx, gy + gh, gx + gw, gy + gh, 1.0, BLACK);
+
+        draw_text(self.eje_x.nombre(), gx, y0 + ALTO_PANEL - 6.0, 14.0, BLACK);
+        draw_text(self.eje_y.nombre(), gx + 4.0, gy + 10.0, 14.0, DARKGRAY);
+
+        let puntos = puntos_plano_fase(reportes, self.eje_x, self.eje_y);
+        if puntos.is_empty() {
+            return;
+        }
+        let (max_x, max_y) = limites_plano_fase(&puntos);
+        draw_text(&format!("{:.0}", max_x), gx + gw - 20.0, gy + gh + 14.0, 14.0, BLACK);
+        draw_text(&format!("{:.0}", max_y), x0 + 2.0, gy + 10.0, 14.0, BLACK);
+
+        let escalar = |(x, y): (f32, f32)| (gx + x / max_x * gw, gy + gh - y / max_y * gh);
+
+        // Los tramos antiguos se dibujan mas transparentes para ver el sentido del ciclo
+        let n = puntos.len();
+        for (i, par) in puntos.windows(2).enumerate() {
+            let (ax, ay) = escalar(par[0]);
+            let (bx, by) = escalar(par[1]);
+            let alfa = 0.2 + 0.8 * (i + 1) as f32 / n as f32;
+            draw_line(ax, ay, bx, by, 1.5, Color::new(0.0, 0.3, 0.8, alfa));
+        }
+
+        let (sx, sy) = escalar(puntos[0]);
+        let (ex, ey) = escalar(puntos[n - 1]);
+        draw_circle(sx, sy, 3.0, DARKGREEN);
+        draw_circle(ex, ey, 4.0, RED);
+    }
+}
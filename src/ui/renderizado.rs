@@ -0,0 +1,57 @@
+use macroquad::prelude::*;
+
+use crate::entidades::{Terreno, TipoCelda, Sexo};
+use crate::utilidades::configuraciones::{ANCHO_MUNDO, ALTO_MUNDO};
+use crate::utilidades::renderizado::{Renderer, VistaOrganismo, ClaseVista, Rgba, color_noche, color_celda};
+
+/// Colores de la simulacion en macroquad
+pub fn color(c: Rgba) -> Color {
+    Color::from_rgba(c.r, c.g, c.b, c.a)
+}
+
+// ==================== RENDERER DE VENTANA ====================
+// Dibuja con macroquad sobre la camara activa (coordenadas del mundo)
+pub struct RendererMacroquad;
+
+impl Renderer for RendererMacroquad {
+    fn terreno(&mut self, terreno: &Terreno) {
+        let ancho = ANCHO_MUNDO / terreno.columnas() as f32;
+        let alto = ALTO_MUNDO / terreno.filas() as f32;
+        for f in 0..terreno.filas() {
+            for c in 0..terreno.columnas() {
+                let tipo = terreno.celda(c, f);
+                // El campo abierto queda con el fondo por defecto
+                if tipo != TipoCelda::Campo {
+                    draw_rectangle(c as f32 * ancho, f as f32 * alto, ancho, alto, color(color_celda(tipo)));
+                }
+            }
+        }
+    }
+
+    fn organismo(&mut self, v: &VistaOrganismo) {
+        let (relleno, anillo) = v.colores();
+        draw_circle(v.x, v.y, v.r, color(relleno));
+        if v.enfermo {
+            draw_circle_lines(v.x, v.y, v.r + 2.0, 2.0, color(anillo));
+        }
+        match v.clase {
+            // Letra M o H para indicar el sexo
+            ClaseVista::Presa { sexo, .. } => {
+                let letra = if sexo == Sexo::Macho { "M" } else { "H" };
+                draw_text(letra, v.x - 5.0, v.y - 12.0, 16.0, BLACK);
+            }
+            // Tiempo que le falta para volver a atacar
+            ClaseVista::Depredador { .. } => {
+                if v.espera > 0.0 {
+                    draw_text(&format!("{:.1}", v.espera), v.x - 10.0, v.y - 20.0, 16.0, BLACK);
+                }
+            }
+        }
+    }
+
+    fn noche(&mut self, tiempo_del_dia: f32) {
+        if let Some(noche) = color_noche(tiempo_del_dia) {
+            draw_rectangle(0.0, 0.0, ANCHO_MUNDO, ALTO_MUNDO, color(noche));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use macroquad::prelude::*;
+use crate::entidades::Especie;
+use crate::sistemas::eventos::RegistroEvento;
+use crate::utilidades::csv::EstadisticasDiarias;
+use crate::utilidades::renderizado::color_especie;
+use crate::ui::renderizado::color;
+
+const ALTO_PANEL: f32 = 150.0;
+
+type Serie = fn(&EstadisticasDiarias) -> usize;
+
+// ==================== PANEL SERIE TEMPORAL ====================
+pub struct PanelSerie {
+    pub visible: bool,
+}
+
+impl PanelSerie {
+    pub fn new() -> Self {
+        Self { visible: true }
+    }
+
+    // T: mostrar/ocultar
+    pub fn manejar_teclas(&mut self) {
+        if is_key_pressed(KeyCode::T) {
+            self.visible = !self.visible;
+        }
+    }
+
+    /// Conteos por especie y depredadores por dia, con una linea vertical en cada evento
+    pub fn dibujar(&self, reportes: &[EstadisticasDiarias], eventos: &[RegistroEvento]) {
+        if !self.visible {
+            return;
+        }
+
+        // Entre el minimapa (izquierda) y el plano de fase (derecha)
+        let x0 = 220.0;
+        let ancho = (screen_width() - x0 - 320.0).max(150.0);
+        let y0 = screen_height() - ALTO_PANEL - 10.0;
+        draw_rectangle(x0, y0, ancho, ALTO_PANEL, Color::new(1.0, 1.0, 1.0, 0.85));
+        draw_rectangle_lines(x0, y0, ancho, ALTO_PANEL, 1.0, BLACK);
+
+        if reportes.is_empty() {
+            return;
+        }
+        let gx = x0 + 5.0;
+        let gy = y0 + 18.0;
+        let gw = ancho - 10.0;
+        let gh = ALTO_PANEL - 23.0;
+
+        let dia_min = reportes[0].dia as f32;
+        let dia_max = (reportes[reportes.len() - 1].dia as f32).max(dia_min + 1.0);
+        let max_y = reportes.iter()
+            .map(|r| r.conteo_conejos.max(r.conteo_ratones).max(r.conteo_ardillas).max(r.depredadores_vivos))
+            .max()
+            .unwrap_or(1)
+            .max(1) as f32;
+        let px = |dia: f32| gx + (dia - dia_min) / (dia_max - dia_min) * gw;
+        let py = |v: f32| gy + gh - v / max_y * gh;
+
+        // Marcas de eventos
+        for ev in eventos.iter().filter(|e| e.dia as f32 >= dia_min) {
+            let x = px(ev.dia as f32);
+            draw_line(x, gy, x, gy + gh, 1.0, Color::new(0.5, 0.0, 0.5, 0.6));
+            let etiqueta: String = ev.evento.chars().take(3).collect();
+            draw_text(&etiqueta, x + 2.0, gy + 10.0, 14.0, PURPLE);
+        }
+
+        let series: [(Serie, Color); 4] = [
+            (|r| r.conteo_conejos, color(color_especie(Especie::Conejo))),
+            (|r| r.conteo_ratones, color(color_especie(Especie::Raton))),
+            (|r| r.conteo_ardillas, color(color_especie(Especie::Ardilla))),
+            (|r| r.depredadores_vivos, RED),
+        ];
+        for (valor, color) in series {
+            for par in reportes.windows(2) {
+                draw_line(
+                    px(par[0].dia as f32), py(valor(&par[0]) as f32),
+                    px(par[1].dia as f32), py(valor(&par[1]) as f32),
+                    1.5, color,
+                );
+            }
+        }
+        draw_text(&format!("Serie temporal (máx {:.0})", max_y), x0 + 5.0, y0 + 13.0, 14.0, BLACK);
+    }
+}
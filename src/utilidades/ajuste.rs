@@ -0,0 +1,109 @@
+// ==================== AJUSTE POR MINIMOS CUADRADOS ====================
+
+/// Minimiza f con Nelder-Mead (simplex) partiendo de `inicio`, con un paso inicial por parametro
+pub fn minimizar(f: impl Fn(&[f64]) -> f64, inicio: &[f64], paso: &[f64], iteraciones: usize) -> Vec<f64> {
+    let n = inicio.len();
+    let mut simplex: Vec<Vec<f64>> = vec![inicio.to_vec()];
+    for i in 0..n {
+        let mut v = inicio.to_vec();
+        v[i] += paso[i];
+        simplex.push(v);
+    }
+    let mut valores: Vec<f64> = simplex.iter().map(|v| f(v)).collect();
+
+    for _ in 0..iteraciones {
+        // Ordenar de mejor a peor
+        let mut orden: Vec<usize> = (0..=n).collect();
+        orden.sort_by(|&a, &b| valores[a].total_cmp(&valores[b]));
+        simplex = orden.iter().map(|&i| simplex[i].clone()).collect();
+        valores = orden.iter().map(|&i| valores[i]).collect();
+        if (valores[n] - valores[0]).abs() <= 1e-12 * (1.0 + valores[0].abs()) {
+            break;
+        }
+
+        let centroide: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|v| v[j]).sum::<f64>() / n as f64)
+            .collect();
+        let punto = |t: f64| -> Vec<f64> {
+            (0..n).map(|j| centroide[j] + t * (simplex[n][j] - centroide[j])).collect()
+        };
+
+        let reflejado = punto(-1.0);
+        let f_r = f(&reflejado);
+        if f_r < valores[0] {
+            let expandido = punto(-2.0);
+            let f_e = f(&expandido);
+            if f_e < f_r {
+                simplex[n] = expandido;
+                valores[n] = f_e;
+            } else {
+                simplex[n] = reflejado;
+                valores[n] = f_r;
+            }
+        } else if f_r < valores[n - 1] {
+            simplex[n] = reflejado;
+            valores[n] = f_r;
+        } else {
+            let contraido = punto(0.5);
+            let f_c = f(&contraido);
+            if f_c < valores[n] {
+                simplex[n] = contraido;
+                valores[n] = f_c;
+            } else {
+                // Encoger todo hacia el mejor vertice
+                for i in 1..=n {
+                    simplex[i] = (0..n).map(|j| simplex[0][j] + 0.5 * (simplex[i][j] - simplex[0][j])).collect();
+                    valores[i] = f(&simplex[i]);
+                }
+            }
+        }
+    }
+
+    let mejor = (0..=n).min_by(|&a, &b| valores[a].total_cmp(&valores[b])).unwrap();
+    simplex[mejor].clone()
+}
+
+/// Suma de cuadrados de los residuos
+pub fn suma_cuadrados(observados: &[f64], ajustados: &[f64]) -> f64 {
+    observados.iter().zip(ajustados).map(|(o, a)| (o - a).powi(2)).sum()
+}
+
+/// Coeficiente de determinacion R²; 0 si los observados no varian
+pub fn r_cuadrado(observados: &[f64], ajustados: &[f64]) -> f64 {
+    if observados.is_empty() {
+        return 0.0;
+    }
+    let media = observados.iter().sum::<f64>() / observados.len() as f64;
+    let total: f64 = observados.iter().map(|o| (o - media).powi(2)).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    1.0 - suma_cuadrados(observados, ajustados) / total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nelder_mead_encuentra_el_minimo_de_una_cuadratica() {
+        let f = |p: &[f64]| (p[0] - 3.0).powi(2) + 10.0 * (p[1] + 1.0).powi(2);
+        let p = minimizar(f, &[0.0, 0.0], &[1.0, 1.0], 500);
+        assert!((p[0] - 3.0).abs() < 1e-4 && (p[1] + 1.0).abs() < 1e-4, "{:?}", p);
+    }
+
+    #[test]
+    fn nelder_mead_sigue_el_valle_de_rosenbrock() {
+        let f = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+        let p = minimizar(f, &[-1.2, 1.0], &[0.5, 0.5], 5000);
+        assert!((p[0] - 1.0).abs() < 1e-3 && (p[1] - 1.0).abs() < 1e-3, "{:?}", p);
+    }
+
+    #[test]
+    fn r_cuadrado_de_ajuste_perfecto_y_de_la_media() {
+        let obs = [1.0, 2.0, 4.0, 3.0];
+        assert_eq!(r_cuadrado(&obs, &obs), 1.0);
+        assert!(r_cuadrado(&obs, &[2.5; 4]).abs() < 1e-12);
+        assert_eq!(r_cuadrado(&[5.0; 3], &[4.0; 3]), 0.0);
+    }
+}
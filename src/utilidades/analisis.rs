@@ -0,0 +1,237 @@
+use std::fs;
+use serde::Serialize;
+
+use crate::utilidades::csv::EstadisticasDiarias;
+
+// Autocorrelacion minima para aceptar un pico como periodo de oscilacion
+const UMBRAL_AUTOCORRELACION: f64 = 0.1;
+
+// ==================== RESUMEN DE SERIES ====================
+#[derive(Clone, Debug, Serialize)]
+pub struct ResumenSerie {
+    pub nombre: &'static str,
+    pub media: f64,
+    pub desviacion: f64,
+    pub coeficiente_variacion: Option<f64>,
+    pub minimo: f64,
+    pub dia_minimo: u32,
+    pub maximo: f64,
+    pub amplitud: f64, // (maximo - minimo) / 2
+    pub periodo_dias: Option<u32>,
+    pub dia_extincion: Option<u32>, // primer dia en cero tras haber estado presente
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ResumenCorrida {
+    pub dias: usize,
+    pub primer_dia: u32,
+    pub ultimo_dia: u32,
+    pub series: Vec<ResumenSerie>,
+    pub primera_extincion: Option<PrimeraExtincion>,
+    /// Desfase (dias) en que la serie de depredadores mejor correlaciona con la de presas;
+    /// positivo si los depredadores van por detras
+    pub desfase_presas_depredadores: Option<i32>,
+    pub correlacion_en_desfase: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PrimeraExtincion {
+    pub serie: &'static str,
+    pub dia: u32,
+}
+
+fn series(reportes: &[EstadisticasDiarias]) -> Vec<(&'static str, Vec<f64>)> {
+    let serie = |f: fn(&EstadisticasDiarias) -> f64| reportes.iter().map(f).collect::<Vec<f64>>();
+    vec![
+        ("conejos", serie(|r| r.conteo_conejos as f64)),
+        ("ratones", serie(|r| r.conteo_ratones as f64)),
+        ("ardillas", serie(|r| r.conteo_ardillas as f64)),
+        ("presas_total", serie(|r| r.conteo_total as f64)),
+        ("depredadores", serie(|r| r.depredadores_vivos as f64)),
+    ]
+}
+
+fn media(v: &[f64]) -> f64 {
+    v.iter().sum::<f64>() / v.len() as f64
+}
+
+fn desviacion(v: &[f64]) -> f64 {
+    let m = media(v);
+    (v.iter().map(|x| (x - m).powi(2)).sum::<f64>() / v.len() as f64).sqrt()
+}
+
+/// Autocorrelacion normalizada para un desfase
+fn autocorrelacion(v: &[f64], desfase: usize) -> f64 {
+    correlacion_cruzada(v, v, desfase as i32)
+}
+
+/// Correlacion de a[t] con b[t + desfase] sobre la parte que se solapa
+pub fn correlacion_cruzada(a: &[f64], b: &[f64], desfase: i32) -> f64 {
+    let (ma, mb) = (media(a), media(b));
+    let (sa, sb) = (desviacion(a), desviacion(b));
+    if sa == 0.0 || sb == 0.0 {
+        return 0.0;
+    }
+    let n = a.len().min(b.len()) as i32;
+    let suma: f64 = (0..n)
+        .filter(|&t| t + desfase >= 0 && t + desfase < n)
+        .map(|t| (a[t as usize] - ma) * (b[(t + desfase) as usize] - mb))
+        .sum();
+    suma / (n as f64 * sa * sb)
+}
+
+/// Serie con un valor por dia desde el primer al ultimo dia de `dias`; los dias que faltan
+/// (p. ej. conteos de campo) se interpolan linealmente entre las observaciones vecinas
+pub fn serie_diaria(dias: &[u32], v: &[f64]) -> Vec<f64> {
+    let Some(&primero) = v.first() else { return Vec::new() };
+    let mut diaria = vec![primero];
+    let (mut dia_previo, mut x_previo) = (dias[0], primero);
+    for (&dia, &x) in dias.iter().zip(v).skip(1) {
+        if dia <= dia_previo {
+            continue; // dia repetido o fuera de orden
+        }
+        let salto = (dia - dia_previo) as f64;
+        for k in 1..=(dia - dia_previo) {
+            diaria.push(x_previo + (x - x_previo) * k as f64 / salto);
+        }
+        (dia_previo, x_previo) = (dia, x);
+    }
+    diaria
+}
+
+/// Periodo en dias como el primer maximo local de la autocorrelacion despues de que esta cruza el cero
+pub fn periodo(dias: &[u32], v: &[f64]) -> Option<u32> {
+    let v = serie_diaria(dias, v);
+    let n = v.len();
+    if n < 4 || desviacion(&v) == 0.0 {
+        return None;
+    }
+    let acf: Vec<f64> = (0..n / 2).map(|k| autocorrelacion(&v, k)).collect();
+    let cruce = acf.iter().position(|&c| c < 0.0)?;
+    (cruce + 1..acf.len().saturating_sub(1))
+        .find(|&k| acf[k] > acf[k - 1] && acf[k] >= acf[k + 1] && acf[k] > UMBRAL_AUTOCORRELACION)
+        .map(|k| k as u32)
+}
+
+fn resumir_serie(nombre: &'static str, v: &[f64], dias: &[u32]) -> ResumenSerie {
+    let m = media(v);
+    let d = desviacion(v);
+    let (i_min, &minimo) = v.iter().enumerate().min_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+    let maximo = v.iter().cloned().fold(f64::MIN, f64::max);
+    let presente = v.iter().position(|&x| x > 0.0);
+    let dia_extincion = presente
+        .and_then(|i| v[i..].iter().position(|&x| x <= 0.0).map(|j| dias[i + j]));
+    ResumenSerie {
+        nombre,
+        media: m,
+        desviacion: d,
+        coeficiente_variacion: (m > 0.0).then_some(d / m),
+        minimo,
+        dia_minimo: dias[i_min],
+        maximo,
+        amplitud: (maximo - minimo) / 2.0,
+        periodo_dias: periodo(dias, v),
+        dia_extincion,
+    }
+}
+
+/// Resumen de una corrida: estadisticas por serie, primera extincion y desfase presa-depredador
+pub fn analizar_reportes(reportes: &[EstadisticasDiarias]) -> Result<ResumenCorrida, String> {
+    if reportes.is_empty() {
+        return Err("el archivo de reportes no tiene dias".to_string());
+    }
+    let dias: Vec<u32> = reportes.iter().map(|r| r.dia).collect();
+    let series = series(reportes);
+    let resumenes: Vec<ResumenSerie> = series.iter().map(|(n, v)| resumir_serie(n, v, &dias)).collect();
+
+    let primera_extincion = resumenes.iter()
+        .filter_map(|r| r.dia_extincion.map(|dia| PrimeraExtincion { serie: r.nombre, dia }))
+        .min_by_key(|e| e.dia);
+
+    // Desfase en dias: las dos series se llevan a un valor por dia antes de correlacionar
+    let presas = serie_diaria(&dias, &series[3].1);
+    let depredadores = serie_diaria(&dias, &series[4].1);
+    let max_desfase = (presas.len() / 2) as i32;
+    let mejor = (desviacion(&presas) > 0.0 && desviacion(&depredadores) > 0.0)
+        .then(|| (-max_desfase..=max_desfase)
+            .map(|k| (k, correlacion_cruzada(&presas, &depredadores, k)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap());
+
+    Ok(ResumenCorrida {
+        dias: reportes.len(),
+        primer_dia: dias[0],
+        ultimo_dia: *dias.last().unwrap(),
+        series: resumenes,
+        primera_extincion,
+        desfase_presas_depredadores: mejor.map(|m| m.0),
+        correlacion_en_desfase: mejor.map(|m| m.1),
+    })
+}
+
+pub fn guardar_resumen_json(resumen: &ResumenCorrida, ruta: &str) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(resumen).map_err(std::io::Error::other)?;
+    fs::write(ruta, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seno(dias: &[u32], periodo: f64) -> Vec<f64> {
+        dias.iter().map(|&d| 50.0 + 20.0 * (2.0 * std::f64::consts::PI * d as f64 / periodo).sin()).collect()
+    }
+
+    #[test]
+    fn periodo_de_un_seno() {
+        let dias: Vec<u32> = (1..=200).collect();
+        assert_eq!(periodo(&dias, &seno(&dias, 25.0)), Some(25));
+        assert_eq!(periodo(&dias, &[7.0; 200]), None);
+    }
+
+    #[test]
+    fn periodo_en_dias_con_huecos() {
+        // Un conteo cada 3 dias: el periodo sigue saliendo en dias, no en filas
+        let dias: Vec<u32> = (0..70).map(|i| 1 + 3 * i).collect();
+        assert_eq!(periodo(&dias, &seno(&dias, 30.0)), Some(30));
+    }
+
+    #[test]
+    fn serie_diaria_interpola_los_dias_que_faltan() {
+        assert_eq!(serie_diaria(&[1, 2, 5], &[0.0, 3.0, 6.0]), vec![0.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn correlacion_cruzada_encuentra_el_desfase_de_una_copia_retrasada() {
+        let dias: Vec<u32> = (0..120).collect();
+        let a = seno(&dias, 40.0);
+        let b: Vec<f64> = dias.iter().map(|&d| a[d.saturating_sub(6) as usize]).collect();
+        let mejor = (-20..=20).max_by(|&x, &y| correlacion_cruzada(&a, &b, x).total_cmp(&correlacion_cruzada(&a, &b, y)));
+        assert_eq!(mejor, Some(6));
+        assert!(correlacion_cruzada(&a, &b, 6) > 0.9);
+    }
+
+    #[test]
+    fn detecta_extinciones_y_desfase_en_los_reportes() {
+        let reportes: Vec<EstadisticasDiarias> = (1..=120u32).map(|dia| {
+            let presas = (100.0 + 40.0 * (2.0 * std::f64::consts::PI * dia as f64 / 40.0).sin()) as usize;
+            let retrasado = (100.0 + 40.0 * (2.0 * std::f64::consts::PI * (dia as f64 - 8.0) / 40.0).sin()) as usize;
+            EstadisticasDiarias {
+                dia,
+                conteo_conejos: if dia < 50 { 10 } else { 0 },
+                conteo_ratones: if dia < 90 { 5 } else { 0 },
+                conteo_total: presas,
+                depredadores_vivos: retrasado / 10,
+                ..Default::default()
+            }
+        }).collect();
+        let resumen = analizar_reportes(&reportes).unwrap();
+        let extincion = |nombre: &str| resumen.series.iter().find(|s| s.nombre == nombre).unwrap().dia_extincion;
+        assert_eq!(extincion("conejos"), Some(50));
+        assert_eq!(extincion("ratones"), Some(90));
+        assert_eq!(extincion("ardillas"), None); // nunca estuvo presente
+        let primera = resumen.primera_extincion.unwrap();
+        assert_eq!((primera.serie, primera.dia), ("conejos", 50));
+        assert_eq!(resumen.desfase_presas_depredadores, Some(8));
+    }
+}
@@ -1,8 +1,119 @@
+use crate::entidades::{EstrategiaCaza, TipoCelda};
+use crate::utilidades::salidas::FormatoReportes;
+use crate::utilidades::grabacion::FormatoGrabacion;
+
 // ==================== CONFIGURACIONES ====================
 
 // Dia 
 pub const DURACION_DIA: f32 = 1.0;
 
+// Dia y noche (fraccion de DURACION_DIA con luz)
+pub const FRACCION_DIURNA: f32 = 0.5;
+
+// Año y estaciones (fases en fraccion del año; 0.0 = inicio de primavera)
+pub const DURACION_ANIO: u32 = 120;
+pub const AMPLITUD_VEGETACION: f32 = 0.6;
+pub const PICO_VEGETACION: f32 = 0.3;
+pub const AMPLITUD_ENFERMEDAD: f32 = 0.5;
+pub const PICO_ENFERMEDAD: f32 = 0.85;
+pub const AMPLITUD_CONSUMO_DEPREDADOR: f32 = 0.3;
+pub const PICO_CONSUMO_DEPREDADOR: f32 = 0.85;
+
+// Salida de reportes (csv, json, jsonl o sqlite; las rutas no llevan extension salvo la base de datos)
+pub const FORMATO_REPORTES: FormatoReportes = FormatoReportes::Csv;
+pub const RUTA_REPORTES: &str = "reportes";
+pub const RUTA_EVENTOS: &str = "eventos";
+pub const RUTA_BITACORA: &str = "bitacora_depredadores";
+pub const RUTA_BASE_DATOS: &str = "simulacion.db";
+
+// Piramide de edades por especie y sexo (tabla aparte; sin interfaz tambien con --piramide)
+pub const PIRAMIDE_EDADES: bool = false;
+pub const RUTA_PIRAMIDE: &str = "piramide_edades";
+pub const ANCHO_CLASE_EDAD: u32 = 2;
+
+// Mapas de densidad: rejilla sobre el mundo y dias que se acumulan (sin interfaz se exportan a RUTA_DENSIDAD)
+pub const COLUMNAS_DENSIDAD: usize = 40;
+pub const FILAS_DENSIDAD: usize = 30;
+pub const DIAS_DENSIDAD: usize = 7;
+pub const RUTA_DENSIDAD: &str = "densidad";
+
+// Grabacion (R en la ventana, --grabar sin interfaz): uno de cada CUADROS_POR_CAPTURA cuadros
+// como PNG numerados en RUTA_GRABACION/ o en RUTA_GRABACION.gif; sin interfaz a ESCALA_GRABACION del mundo
+pub const FORMATO_GRABACION: FormatoGrabacion = FormatoGrabacion::Png;
+pub const CUADROS_POR_CAPTURA: u32 = 10;
+pub const RUTA_GRABACION: &str = "grabacion";
+pub const ESCALA_GRABACION: f32 = 0.5;
+
+// Poblacion inicial
+pub const PRESAS_INICIALES: usize = 50;
+pub const DEPREDADORES_INICIALES: usize = 1;
+
+// Sin interfaz: paso fijo en segundos simulados (un cuadro a 60 fps)
+pub const PASO_SIN_INTERFAZ: f32 = 1.0 / 60.0;
+pub const DIAS_SIN_INTERFAZ: u32 = 365;
+
+// Paso en paralelo (rayon): da el mismo resultado que el serial (--serial sin interfaz).
+// El indice espacial agrupa a las presas en celdas de TAMANO_CELDA_INDICE
+pub const PASO_PARALELO: bool = true;
+pub const TAMANO_CELDA_INDICE: f32 = 40.0;
+// Rendimiento (--rendimiento): pasos medidos con cada cantidad de hilos
+pub const PASOS_RENDIMIENTO: u32 = 120;
+
+// Terminal (--tui): pasos por cuadro que se eligen con +/- y espera entre cuadros
+pub const VELOCIDADES_TERMINAL: [u32; 6] = [1, 5, 15, 60, 240, 600];
+pub const CUADRO_TERMINAL_MS: u64 = 50;
+
+// Respuesta funcional: densidad en presas por area de 100x100 del mundo
+pub const AREA_UNIDAD_DENSIDAD: f32 = 10_000.0;
+pub const PRESAS_RESPUESTA_FUNCIONAL: [usize; 7] = [2, 5, 10, 20, 40, 80, 160];
+pub const DIAS_RESPUESTA_FUNCIONAL: u32 = 30;
+pub const DIAS_CALENTAMIENTO_RESPUESTA: u32 = 2;
+
+// Mundo (independiente del tamaño de la ventana)
+pub const ANCHO_MUNDO: f32 = 800.0;
+pub const ALTO_MUNDO: f32 = 600.0;
+
+// Bordes del mundo
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModoBorde {
+    Reflectivo, // rebota y se mantiene dentro del mundo
+    Toroidal,   // sale por un lado y entra por el opuesto
+    Absorbente, // al salir emigra y se retira de la simulacion
+}
+pub const MODO_BORDE: ModoBorde = ModoBorde::Reflectivo;
+
+// Eventos (probabilidad diaria de los aleatorios y efectos por defecto)
+pub const RUTA_GUION_EVENTOS: &str = "eventos.txt";
+pub const PROB_SEQUIA: f32 = 0.004;
+pub const DURACION_SEQUIA: u32 = 15;
+pub const FACTOR_SEQUIA: f32 = 0.3;
+pub const PROB_INVIERNO_DURO: f32 = 0.01; // solo en invierno
+pub const DURACION_INVIERNO_DURO: u32 = 15;
+pub const FACTOR_INVIERNO_DURO: f32 = 1.6;
+pub const PROB_INUNDACION: f32 = 0.003;
+pub const DESBORDE_INUNDACION: usize = 2; // celdas alrededor del agua que alcanza una inundacion
+pub const FRACCION_MUERTES_INUNDACION: f32 = 0.5;
+pub const PROB_BROTE: f32 = 0.004;
+pub const FRACCION_BROTE: f32 = 0.2;
+
+// Intervenciones de manejo (escenario y teclas)
+pub const RUTA_ESCENARIO: &str = "escenario.txt";
+pub const CANTIDAD_SACRIFICIO_TECLA: usize = 5;
+pub const CANTIDAD_LIBERACION_TECLA: usize = 5;
+pub const FRACCION_VACUNACION_TECLA: f32 = 0.5;
+pub const DIAS_INMUNIDAD_VACUNA: u32 = 30;
+
+// Terreno (PNG o grilla de texto; si no existe el archivo todo es campo abierto)
+pub const RUTA_TERRENO: &str = "terreno.txt";
+// Paleta del terreno (RGB): con ella se dibuja y con ella se leen los mapas PNG (cada pixel toma el tipo mas parecido)
+pub const PALETA_TERRENO: [(TipoCelda, [u8; 3]); 5] = [
+    (TipoCelda::Campo, [200, 230, 160]),
+    (TipoCelda::Bosque, [40, 110, 50]),
+    (TipoCelda::Agua, [70, 130, 220]),
+    (TipoCelda::Rocas, [120, 120, 120]),
+    (TipoCelda::Madriguera, [130, 90, 50]),
+];
+
 // Presas
 pub const GOMPERTZ_A_CONEJO: f32 = 10.0;
 pub const GOMPERTZ_B_CONEJO: f32 = 0.2;
@@ -36,6 +147,20 @@ pub const POBLACION_MAXIMA_CONEJO: usize = 30;
 pub const POBLACION_MAXIMA_RATON: usize = 40;
 pub const POBLACION_MAXIMA_ARDILLA: usize = 25;
 
+pub const VENTANA_REPRODUCCION_CONEJO: (f32, f32) = (0.0, 0.6);
+pub const VENTANA_REPRODUCCION_RATON: (f32, f32) = (0.0, 1.0);
+pub const VENTANA_REPRODUCCION_ARDILLA: (f32, f32) = (0.05, 0.45);
+
+// Actividad (dia, noche): escala el movimiento y la tasa de encuentros con depredadores
+pub const ACTIVIDAD_CONEJO: (f32, f32) = (1.0, 0.4);
+pub const ACTIVIDAD_RATON: (f32, f32) = (0.2, 1.0);
+pub const ACTIVIDAD_ARDILLA: (f32, f32) = (1.0, 0.1);
+pub const ACTIVIDAD_ZORRO: (f32, f32) = (0.5, 1.0);
+pub const ACTIVIDAD_LINCE: (f32, f32) = (0.7, 0.8);
+pub const ACTIVIDAD_BUHO: (f32, f32) = (0.05, 1.0); // nocturno
+pub const ACTIVIDAD_COMADREJA: (f32, f32) = (0.8, 0.6);
+pub const ACTIVIDAD_HALCON: (f32, f32) = (1.0, 0.05); // diurno
+
 pub const PROB_CRIAS_CONEJO: [f32; 7] = [0.02, 0.15, 0.40, 0.25, 0.10, 0.06, 0.02];
 pub const PROB_CRIAS_RATON: [f32; 5] = [0.10, 0.30, 0.40, 0.15, 0.05];
 pub const PROB_CRIAS_ARDILLA: [f32; 4] = [0.20, 0.50, 0.20, 0.10];
@@ -53,6 +178,23 @@ pub const DIAS_INMUNIDAD: u32 = 7;
 pub const TIEMPO_ESPERA_COMIDA: f32 = 1.0;
 pub const RADIO_DEPREDADOR: f32 = 12.0;
 pub const VEL_MAX_DEPREDADOR: f32 = 2.0;
+pub const ESTRATEGIA_ZORRO: EstrategiaCaza = EstrategiaCaza::MasPesada;
+pub const ESTRATEGIA_LINCE: EstrategiaCaza = EstrategiaCaza::Emboscada;
+pub const ESTRATEGIA_BUHO: EstrategiaCaza = EstrategiaCaza::MasCercana;
+pub const ESTRATEGIA_COMADREJA: EstrategiaCaza = EstrategiaCaza::Persecucion;
+pub const ESTRATEGIA_HALCON: EstrategiaCaza = EstrategiaCaza::ForrajeoOptimo;
+pub const RADIO_EMBOSCADA: f32 = 80.0;
+pub const RADIO_VISION_DEPREDADOR: f32 = 150.0; // se reduce segun la visibilidad de la celda de la presa
+
+// Captura: probabilidad base de exito de un ataque, ajustada por velocidad, salud y peso
+pub const PROB_CAPTURA_BASE: f32 = 0.6;
+pub const FACTOR_CAPTURA_PRESA_ENFERMA: f32 = 1.5;
+pub const FACTOR_CAPTURA_DEPREDADOR_ENFERMO: f32 = 0.5;
+pub const PESO_REFERENCIA_CAPTURA: f32 = 6.0; // presas mas pesadas se defienden mejor
+pub const PESO_COBERTURA_CAPTURA: f32 = 0.5; // cuanto resta la cobertura (1 - visibilidad) de la celda de la presa
+pub const COSTO_PERSECUCION: f32 = 0.6; // reserva gastada por segundo persiguiendo
+pub const COSTO_ATAQUE: f32 = 0.2; // reserva gastada en cada intento de captura
+pub const TIEMPO_ESPERA_ATAQUE_FALLIDO: f32 = 0.5;
 
 // Probabilidades machos
 pub const PROB_MACHO_CONEJO: f32 = 0.55;
@@ -1,6 +1,8 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize)]
+// Las columnas que falten al leer (p. ej. conteos de campo) quedan en cero
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EstadisticasDiarias {
     pub dia: u32,
     pub conteo_conejos: usize,
@@ -14,9 +16,46 @@ pub struct EstadisticasDiarias {
     pub reproducciones: u32,
     pub depredadores_enfermos: usize,
     pub depredadores_vivos: usize,
+    // Columnas agregadas despues, siempre al final para no mover las anteriores
+    pub reserva_total_depredadores: f32,
+    pub emigraciones: u32,
+    pub estacion: String,
+    pub muertes_por_eventos: u32,
+    pub capturas_mas_pesada: u32,
+    pub capturas_mas_cercana: u32,
+    pub capturas_forrajeo_optimo: u32,
+    pub capturas_emboscada: u32,
+    pub capturas_persecucion: u32,
+    pub ataques_fallidos: u32,
+    // Detalle por especie: proporcion de machos, edad y peso (media y mediana),
+    // presas en modo_reproduccion, enfermas y nacidas en el dia
+    pub proporcion_machos_conejos: f32,
+    pub edad_media_conejos: f32,
+    pub edad_mediana_conejos: f32,
+    pub peso_medio_conejos: f32,
+    pub peso_mediano_conejos: f32,
+    pub en_reproduccion_conejos: usize,
+    pub enfermos_conejos: usize,
+    pub nacimientos_conejos: u32,
+    pub proporcion_machos_ratones: f32,
+    pub edad_media_ratones: f32,
+    pub edad_mediana_ratones: f32,
+    pub peso_medio_ratones: f32,
+    pub peso_mediano_ratones: f32,
+    pub en_reproduccion_ratones: usize,
+    pub enfermos_ratones: usize,
+    pub nacimientos_ratones: u32,
+    pub proporcion_machos_ardillas: f32,
+    pub edad_media_ardillas: f32,
+    pub edad_mediana_ardillas: f32,
+    pub peso_medio_ardillas: f32,
+    pub peso_mediano_ardillas: f32,
+    pub en_reproduccion_ardillas: usize,
+    pub enfermos_ardillas: usize,
+    pub nacimientos_ardillas: u32,
 }
 
-pub fn guardar_reportes_csv(reportes: &Vec<EstadisticasDiarias>, ruta: &str) -> csv::Result<()> {
+pub fn guardar_reportes_csv(reportes: &[EstadisticasDiarias], ruta: &str) -> csv::Result<()> {
     let mut wtr = csv::Writer::from_path(ruta)?;
     for rep in reportes {
         wtr.serialize(rep)?; // convierte struct -> fila CSV
@@ -24,3 +63,37 @@ pub fn guardar_reportes_csv(reportes: &Vec<EstadisticasDiarias>, ruta: &str) ->
     wtr.flush()?;
     Ok(())
 }
+
+/// Lee un reportes.csv de este simulador o una serie externa con el mismo esquema
+/// (las lineas que empiezan con '#' son el encabezado de metadatos y se ignoran)
+pub fn cargar_reportes_csv(ruta: &str) -> csv::Result<Vec<EstadisticasDiarias>> {
+    let mut rdr = csv::ReaderBuilder::new().comment(Some(b'#')).from_path(ruta)?;
+    rdr.deserialize().collect()
+}
+
+#[derive(Serialize)]
+struct FilaComparacion {
+    dia: u32,
+    presas_abm: usize,
+    presas_edo: usize,
+    depredadores_abm: usize,
+    depredadores_edo: usize,
+}
+
+/// Dos series lado a lado por dia (p. ej. modelo de agentes y campo medio); solo los dias presentes en ambas
+pub fn guardar_comparacion_csv(abm: &[EstadisticasDiarias], edo: &[EstadisticasDiarias], ruta: &str) -> csv::Result<()> {
+    let mut wtr = csv::Writer::from_path(ruta)?;
+    for a in abm {
+        if let Some(e) = edo.iter().find(|e| e.dia == a.dia) {
+            wtr.serialize(FilaComparacion {
+                dia: a.dia,
+                presas_abm: a.conteo_total,
+                presas_edo: e.conteo_total,
+                depredadores_abm: a.depredadores_vivos,
+                depredadores_edo: e.depredadores_vivos,
+            })?;
+        }
+    }
+    wtr.flush()?;
+    Ok(())
+}
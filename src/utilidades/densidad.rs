@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+
+use crate::entidades::{Mundo, Especie};
+use crate::utilidades::configuraciones::{ANCHO_MUNDO, ALTO_MUNDO};
+
+// ==================== CAPAS ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CapaDensidad { Conejos, Ratones, Ardillas, Depredadores, Capturas, Infecciones }
+
+impl CapaDensidad {
+    pub const TODAS: [CapaDensidad; 6] = [
+        CapaDensidad::Conejos,
+        CapaDensidad::Ratones,
+        CapaDensidad::Ardillas,
+        CapaDensidad::Depredadores,
+        CapaDensidad::Capturas,
+        CapaDensidad::Infecciones,
+    ];
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            CapaDensidad::Conejos => "Conejos",
+            CapaDensidad::Ratones => "Ratones",
+            CapaDensidad::Ardillas => "Ardillas",
+            CapaDensidad::Depredadores => "Depredadores",
+            CapaDensidad::Capturas => "Capturas",
+            CapaDensidad::Infecciones => "Infecciones",
+        }
+    }
+
+    // Prefijo de los archivos exportados
+    pub fn archivo(&self) -> &'static str {
+        match self {
+            CapaDensidad::Conejos => "conejos",
+            CapaDensidad::Ratones => "ratones",
+            CapaDensidad::Ardillas => "ardillas",
+            CapaDensidad::Depredadores => "depredadores",
+            CapaDensidad::Capturas => "capturas",
+            CapaDensidad::Infecciones => "infecciones",
+        }
+    }
+
+    pub fn siguiente(&self) -> CapaDensidad {
+        let i = CapaDensidad::TODAS.iter().position(|c| c == self).unwrap();
+        CapaDensidad::TODAS[(i + 1) % CapaDensidad::TODAS.len()]
+    }
+
+    fn indice(&self) -> usize {
+        CapaDensidad::TODAS.iter().position(|c| c == self).unwrap()
+    }
+
+    fn de_especie(especie: Especie) -> CapaDensidad {
+        match especie {
+            Especie::Conejo => CapaDensidad::Conejos,
+            Especie::Raton => CapaDensidad::Ratones,
+            Especie::Ardilla => CapaDensidad::Ardillas,
+        }
+    }
+}
+
+// ==================== MAPA DE DENSIDAD ====================
+// Rejilla de columnas x filas sobre el mundo. Las poblaciones se cuentan al cerrar cada dia;
+// capturas e infecciones se suman donde ocurren. Se guardan los ultimos dias_ventana dias.
+pub struct MapaDensidad {
+    pub columnas: usize,
+    pub filas: usize,
+    pub dias_ventana: usize,
+    actual: Vec<Vec<f32>>,
+    historial: VecDeque<Vec<Vec<f32>>>,
+}
+
+impl MapaDensidad {
+    pub fn new(columnas: usize, filas: usize, dias_ventana: usize) -> Self {
+        let (columnas, filas) = (columnas.max(1), filas.max(1));
+        Self {
+            columnas,
+            filas,
+            dias_ventana: dias_ventana.max(1),
+            actual: vec![vec![0.0; columnas * filas]; CapaDensidad::TODAS.len()],
+            historial: VecDeque::new(),
+        }
+    }
+
+    /// Tamaño de una celda en coordenadas del mundo
+    pub fn tamano_celda(&self) -> (f32, f32) {
+        (ANCHO_MUNDO / self.columnas as f32, ALTO_MUNDO / self.filas as f32)
+    }
+
+    fn celda(&self, x: f32, y: f32) -> usize {
+        let (ancho, alto) = self.tamano_celda();
+        let c = ((x / ancho) as usize).min(self.columnas - 1);
+        let f = ((y / alto) as usize).min(self.filas - 1);
+        f * self.columnas + c
+    }
+
+    /// Suma un suceso (captura, infeccion) en la posicion dada al dia en curso
+    pub fn registrar(&mut self, capa: CapaDensidad, x: f32, y: f32) {
+        let i = self.celda(x, y);
+        self.actual[capa.indice()][i] += 1.0;
+    }
+
+    /// Cuenta las poblaciones (presas por Especie, depredadores por Caza), guarda el dia en el
+    /// historial y empieza uno nuevo
+    pub fn cerrar_dia(&mut self, mundo: &Mundo) {
+        for (i, pos) in mundo.posiciones.iter().filter(|&(i, _)| mundo.esta_vivo(i)) {
+            if let Some(&especie) = mundo.especies.get(i) {
+                self.registrar(CapaDensidad::de_especie(especie), pos.x, pos.y);
+            }
+        }
+        for (_, pos) in mundo.posiciones.iter().filter(|&(i, _)| mundo.esta_vivo(i) && mundo.cazas.tiene(i)) {
+            self.registrar(CapaDensidad::Depredadores, pos.x, pos.y);
+        }
+        let vacio = vec![vec![0.0; self.columnas * self.filas]; CapaDensidad::TODAS.len()];
+        self.historial.push_back(std::mem::replace(&mut self.actual, vacio));
+        while self.historial.len() > self.dias_ventana {
+            self.historial.pop_front();
+        }
+    }
+
+    /// Suma de la capa en los dias guardados, por filas (fila * columnas + columna)
+    pub fn acumulada(&self, capa: CapaDensidad) -> Vec<f32> {
+        let mut total = vec![0.0; self.columnas * self.filas];
+        for dia in &self.historial {
+            for (t, v) in total.iter_mut().zip(&dia[capa.indice()]) {
+                *t += v;
+            }
+        }
+        total
+    }
+
+    /// Exporta todas las capas acumuladas como <capa>_dia_<n>.csv o .pgm en el directorio
+    pub fn exportar(&self, directorio: &str, dia: u32, formato: FormatoDensidad) -> std::io::Result<()> {
+        fs::create_dir_all(directorio)?;
+        for capa in CapaDensidad::TODAS {
+            let ruta = format!("{}/{}_dia_{:04}.{}", directorio, capa.archivo(), dia, formato.extension());
+            let valores = self.acumulada(capa);
+            match formato {
+                FormatoDensidad::Csv => guardar_matriz_csv(&valores, self.columnas, &ruta)?,
+                FormatoDensidad::Pgm => guardar_pgm(&valores, self.columnas, self.filas, &ruta)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// ==================== EXPORTACION ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FormatoDensidad { Csv, Pgm }
+
+impl FormatoDensidad {
+    pub fn desde_nombre(nombre: &str) -> Option<FormatoDensidad> {
+        match nombre.to_lowercase().as_str() {
+            "csv" => Some(FormatoDensidad::Csv),
+            "pgm" => Some(FormatoDensidad::Pgm),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FormatoDensidad::Csv => "csv",
+            FormatoDensidad::Pgm => "pgm",
+        }
+    }
+}
+
+/// Matriz sin encabezado: una linea por fila de la rejilla, de arriba hacia abajo
+pub fn guardar_matriz_csv(valores: &[f32], columnas: usize, ruta: &str) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(ruta)?);
+    for fila in valores.chunks(columnas) {
+        let textos: Vec<String> = fila.iter().map(|v| v.to_string()).collect();
+        writeln!(w, "{}", textos.join(","))?;
+    }
+    w.flush()
+}
+
+/// Imagen PGM binaria en escala de grises; el maximo de la capa queda en blanco
+pub fn guardar_pgm(valores: &[f32], columnas: usize, filas: usize, ruta: &str) -> std::io::Result<()> {
+    let maximo = valores.iter().cloned().fold(0.0, f32::max);
+    let mut w = BufWriter::new(File::create(ruta)?);
+    write!(w, "P5\n{} {}\n255\n", columnas, filas)?;
+    let bytes: Vec<u8> = valores.iter()
+        .map(|v| if maximo > 0.0 { (v / maximo * 255.0).round() as u8 } else { 0 })
+        .collect();
+    w.write_all(&bytes)?;
+    w.flush()
+}
@@ -0,0 +1,52 @@
+// ==================== ECUACIONES DIFERENCIALES ====================
+
+/// Integra dy/dt = derivada(y) con Runge-Kutta 4 y devuelve el estado al final de cada dia
+/// (el primer elemento es y0); cada dia se divide en `sub_pasos` pasos
+pub fn integrar_rk4(derivada: impl Fn(&[f64]) -> Vec<f64>, y0: &[f64], dias: usize, sub_pasos: usize) -> Vec<Vec<f64>> {
+    let dt = 1.0 / sub_pasos as f64;
+    let mut y = y0.to_vec();
+    let mut trayectoria = vec![y.clone()];
+    for _ in 0..dias {
+        for _ in 0..sub_pasos {
+            y = paso_rk4(&derivada, &y, dt);
+        }
+        trayectoria.push(y.clone());
+    }
+    trayectoria
+}
+
+pub fn paso_rk4(derivada: impl Fn(&[f64]) -> Vec<f64>, y: &[f64], dt: f64) -> Vec<f64> {
+    let mas = |a: &[f64], k: &[f64], f: f64| -> Vec<f64> { a.iter().zip(k).map(|(a, k)| a + f * k).collect() };
+    let k1 = derivada(y);
+    let k2 = derivada(&mas(y, &k1, dt / 2.0));
+    let k3 = derivada(&mas(y, &k2, dt / 2.0));
+    let k4 = derivada(&mas(y, &k3, dt));
+    (0..y.len())
+        .map(|i| y[i] + dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rk4_integra_el_decaimiento_exponencial() {
+        let tray = integrar_rk4(|y| vec![-y[0]], &[1.0], 3, 10);
+        assert_eq!(tray.len(), 4);
+        for (t, y) in tray.iter().enumerate() {
+            assert!((y[0] - (-(t as f64)).exp()).abs() < 1e-6, "dia {}: {}", t, y[0]);
+        }
+    }
+
+    #[test]
+    fn rk4_es_de_cuarto_orden() {
+        // Oscilador armonico: y = (cos t, -sin t); el error baja 16 veces al duplicar los pasos
+        let error = |sub_pasos: usize| {
+            let y = integrar_rk4(|y| vec![y[1], -y[0]], &[1.0, 0.0], 5, sub_pasos).pop().unwrap();
+            ((y[0] - 5f64.cos()).powi(2) + (y[1] + 5f64.sin()).powi(2)).sqrt()
+        };
+        let razon = error(4) / error(8);
+        assert!((razon - 16.0).abs() < 2.0, "razon {}", razon);
+    }
+}
@@ -0,0 +1,159 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+
+use crate::utilidades::configuraciones::RUTA_GRABACION;
+use crate::utilidades::renderizado::Rgba;
+
+// ==================== LIENZO ====================
+// Imagen RGBA de arriba hacia abajo; sirve para capturas de pantalla y para dibujar sin ventana
+pub struct Lienzo {
+    pub ancho: usize,
+    pub alto: usize,
+    pub pixeles: Vec<u8>,
+}
+
+impl Lienzo {
+    pub fn new(ancho: usize, alto: usize, fondo: Rgba) -> Self {
+        Self { ancho, alto, pixeles: [fondo.r, fondo.g, fondo.b, fondo.a].repeat(ancho * alto) }
+    }
+
+    // Mezcla el color sobre el pixel segun su transparencia
+    pub fn pintar(&mut self, x: usize, y: usize, color: Rgba) {
+        let i = (y * self.ancho + x) * 4;
+        let alfa = color.opacidad();
+        for (c, f) in self.pixeles[i..i + 3].iter_mut().zip([color.r, color.g, color.b]) {
+            *c = (f as f32 * alfa + *c as f32 * (1.0 - alfa)).round() as u8;
+        }
+        self.pixeles[i + 3] = 255;
+    }
+
+    pub fn rectangulo(&mut self, x: f32, y: f32, ancho: f32, alto: f32, color: Rgba) {
+        let (x0, y0) = (x.max(0.0) as usize, y.max(0.0) as usize);
+        let x1 = ((x + ancho).ceil().max(0.0) as usize).min(self.ancho);
+        let y1 = ((y + alto).ceil().max(0.0) as usize).min(self.alto);
+        for py in y0..y1 {
+            for px in x0..x1 {
+                self.pintar(px, py, color);
+            }
+        }
+    }
+
+    /// Circulo relleno o, con grosor, solo el anillo entre r - grosor y r
+    pub fn circulo(&mut self, cx: f32, cy: f32, r: f32, grosor: Option<f32>, color: Rgba) {
+        let x0 = (cx - r).floor().max(0.0) as usize;
+        let y0 = (cy - r).floor().max(0.0) as usize;
+        let x1 = ((cx + r).ceil().max(0.0) as usize).min(self.ancho);
+        let y1 = ((cy + r).ceil().max(0.0) as usize).min(self.alto);
+        let interior = grosor.map_or(-1.0, |g| (r - g).max(0.0));
+        for py in y0..y1 {
+            for px in x0..x1 {
+                let d = ((px as f32 + 0.5 - cx).powi(2) + (py as f32 + 0.5 - cy).powi(2)).sqrt();
+                if d <= r && d >= interior {
+                    self.pintar(px, py, color);
+                }
+            }
+        }
+    }
+}
+
+// ==================== GRABADORA ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FormatoGrabacion { Png, Gif }
+
+impl FormatoGrabacion {
+    pub fn desde_nombre(nombre: &str) -> Option<FormatoGrabacion> {
+        match nombre.to_lowercase().as_str() {
+            "png" => Some(FormatoGrabacion::Png),
+            "gif" => Some(FormatoGrabacion::Gif),
+            _ => None,
+        }
+    }
+
+    /// Directorio de los PNG numerados o archivo del GIF
+    pub fn ruta(&self) -> String {
+        match self {
+            FormatoGrabacion::Png => format!("{}/", RUTA_GRABACION),
+            FormatoGrabacion::Gif => format!("{}.gif", RUTA_GRABACION),
+        }
+    }
+}
+
+// Guarda uno de cada `cada` cuadros como PNG numerado o como cuadro de un GIF animado.
+// El GIF toma el tamaño del primer cuadro; los de otro tamaño se descartan.
+pub struct Grabadora {
+    formato: FormatoGrabacion,
+    cada: u32,
+    cuadros: u64,
+    guardados: u32,
+    gif: Option<gif::Encoder<BufWriter<File>>>,
+    tamano: (usize, usize),
+}
+
+impl Grabadora {
+    pub fn new(formato: FormatoGrabacion, cada: u32) -> Self {
+        Self { formato, cada: cada.max(1), cuadros: 0, guardados: 0, gif: None, tamano: (0, 0) }
+    }
+
+    pub fn formato(&self) -> FormatoGrabacion { self.formato }
+    pub fn guardados(&self) -> u32 { self.guardados }
+
+    /// Cuenta un cuadro; true si es uno de los que se guardan
+    pub fn toca_capturar(&mut self) -> bool {
+        self.cuadros += 1;
+        (self.cuadros - 1).is_multiple_of(self.cada as u64)
+    }
+
+    pub fn agregar(&mut self, lienzo: &Lienzo) -> Result<(), String> {
+        match self.formato {
+            FormatoGrabacion::Png => {
+                fs::create_dir_all(RUTA_GRABACION).map_err(|e| e.to_string())?;
+                if self.guardados == 0 {
+                    borrar_cuadros_anteriores();
+                }
+                let ruta = format!("{}/cuadro_{:06}.png", RUTA_GRABACION, self.guardados + 1);
+                let archivo = BufWriter::new(File::create(&ruta).map_err(|e| e.to_string())?);
+                let mut codificador = png::Encoder::new(archivo, lienzo.ancho as u32, lienzo.alto as u32);
+                codificador.set_color(png::ColorType::Rgba);
+                codificador.set_depth(png::BitDepth::Eight);
+                let mut escritor = codificador.write_header().map_err(|e| e.to_string())?;
+                escritor.write_image_data(&lienzo.pixeles).map_err(|e| e.to_string())?;
+            }
+            FormatoGrabacion::Gif => {
+                if self.gif.is_none() {
+                    let archivo = BufWriter::new(File::create(self.formato.ruta()).map_err(|e| e.to_string())?);
+                    let mut codificador = gif::Encoder::new(archivo, lienzo.ancho as u16, lienzo.alto as u16, &[])
+                        .map_err(|e| e.to_string())?;
+                    codificador.set_repeat(gif::Repeat::Infinite).map_err(|e| e.to_string())?;
+                    self.gif = Some(codificador);
+                    self.tamano = (lienzo.ancho, lienzo.alto);
+                }
+                if (lienzo.ancho, lienzo.alto) != self.tamano {
+                    return Ok(());
+                }
+                let mut pixeles = lienzo.pixeles.clone();
+                let mut cuadro = gif::Frame::from_rgba_speed(lienzo.ancho as u16, lienzo.alto as u16, &mut pixeles, 10);
+                // A 60 cuadros por segundo, en centesimas
+                cuadro.delay = ((self.cada as f32 * 100.0 / 60.0).round() as u16).max(2);
+                self.gif.as_mut().unwrap().write_frame(&cuadro).map_err(|e| e.to_string())?;
+            }
+        }
+        self.guardados += 1;
+        Ok(())
+    }
+
+    /// Cierra el GIF (escribe el final del archivo); los PNG ya estan completos
+    pub fn finalizar(&mut self) {
+        self.gif = None;
+    }
+}
+
+// Una grabacion nueva no debe quedar mezclada con cuadros sobrantes de una anterior mas larga
+fn borrar_cuadros_anteriores() {
+    let Ok(entradas) = fs::read_dir(RUTA_GRABACION) else { return };
+    for entrada in entradas.flatten() {
+        let nombre = entrada.file_name().to_string_lossy().to_string();
+        if nombre.starts_with("cuadro_") && nombre.ends_with(".png") {
+            let _ = fs::remove_file(entrada.path());
+        }
+    }
+}
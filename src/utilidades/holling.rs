@@ -0,0 +1,158 @@
+use serde::Serialize;
+
+use crate::utilidades::ajuste::{minimizar, r_cuadrado, suma_cuadrados};
+
+// ==================== RESPUESTA FUNCIONAL ====================
+/// Capturas medidas a una densidad de presas mantenida fija
+#[derive(Clone, Debug, Serialize)]
+pub struct PuntoRespuesta {
+    pub presas: usize,
+    pub densidad: f32, // presas por AREA_UNIDAD_DENSIDAD
+    pub dias: u32,
+    pub capturas_por_depredador_dia: f32,
+    pub ataques_fallidos_por_depredador_dia: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModeloHolling { TipoI, TipoII, TipoIII }
+
+impl ModeloHolling {
+    pub const TODOS: [ModeloHolling; 3] = [ModeloHolling::TipoI, ModeloHolling::TipoII, ModeloHolling::TipoIII];
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            ModeloHolling::TipoI => "Holling I",
+            ModeloHolling::TipoII => "Holling II",
+            ModeloHolling::TipoIII => "Holling III",
+        }
+    }
+
+    // Capturas por depredador y dia (T = 1 dia) con tasa de ataque a y tiempo de manipulacion h
+    pub fn evaluar(&self, densidad: f64, a: f64, h: f64) -> f64 {
+        match self {
+            ModeloHolling::TipoI => a * densidad,
+            ModeloHolling::TipoII => a * densidad / (1.0 + a * h * densidad),
+            ModeloHolling::TipoIII => a * densidad * densidad / (1.0 + a * h * densidad * densidad),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AjusteHolling {
+    pub modelo: &'static str,
+    pub tasa_ataque: f64,
+    pub tiempo_manipulacion: f64, // en dias; 0 en el tipo I
+    pub suma_cuadrados: f64,
+    pub r_cuadrado: f64,
+    pub aic: f64, // compara modelos con distinto numero de parametros (menor es mejor)
+}
+
+/// Ajusta el modelo por minimos cuadrados; a y h se buscan en escala logaritmica para que sean positivos
+pub fn ajustar_holling(modelo: ModeloHolling, puntos: &[PuntoRespuesta]) -> AjusteHolling {
+    let n: Vec<f64> = puntos.iter().map(|p| p.densidad as f64).collect();
+    let f: Vec<f64> = puntos.iter().map(|p| p.capturas_por_depredador_dia as f64).collect();
+
+    let (a, h) = match modelo {
+        // Recta por el origen: solucion cerrada
+        ModeloHolling::TipoI => {
+            let nn: f64 = n.iter().map(|x| x * x).sum();
+            let nf: f64 = n.iter().zip(&f).map(|(x, y)| x * y).sum();
+            (if nn > 0.0 { nf / nn } else { 0.0 }, 0.0)
+        }
+        ModeloHolling::TipoII | ModeloHolling::TipoIII => {
+            let exponente = if modelo == ModeloHolling::TipoII { 1 } else { 2 };
+            // Inicio: pendiente en la densidad mas baja y asintota en el maximo observado
+            let a0 = n.iter().zip(&f)
+                .filter(|(x, y)| **x > 0.0 && **y > 0.0)
+                .map(|(x, y)| y / x.powi(exponente))
+                .next()
+                .unwrap_or(1.0);
+            let h0 = 1.0 / f.iter().cloned().fold(1e-3, f64::max);
+            let error = |v: &[f64]| {
+                let ajustados: Vec<f64> = n.iter().map(|&x| modelo.evaluar(x, v[0].exp(), v[1].exp())).collect();
+                suma_cuadrados(&f, &ajustados)
+            };
+            let v = minimizar(error, &[a0.ln(), h0.ln()], &[1.0, 1.0], 2000);
+            (v[0].exp(), v[1].exp())
+        }
+    };
+
+    let ajustados: Vec<f64> = n.iter().map(|&x| modelo.evaluar(x, a, h)).collect();
+    let sce = suma_cuadrados(&f, &ajustados);
+    let observaciones = puntos.len().max(1) as f64;
+    let parametros = if modelo == ModeloHolling::TipoI { 1.0 } else { 2.0 };
+    AjusteHolling {
+        modelo: modelo.nombre(),
+        tasa_ataque: a,
+        tiempo_manipulacion: h,
+        suma_cuadrados: sce,
+        r_cuadrado: r_cuadrado(&f, &ajustados),
+        aic: observaciones * (sce.max(f64::MIN_POSITIVE) / observaciones).ln() + 2.0 * parametros,
+    }
+}
+
+/// El ajuste de menor AIC: un tipo II con h casi nulo empata en error con el tipo I, pero paga su parametro extra
+pub fn mejor_ajuste(ajustes: &[AjusteHolling]) -> Option<&AjusteHolling> {
+    ajustes.iter().min_by(|a, b| a.aic.total_cmp(&b.aic))
+}
+
+pub fn guardar_respuesta_csv(puntos: &[PuntoRespuesta], ruta: &str) -> csv::Result<()> {
+    let mut wtr = csv::Writer::from_path(ruta)?;
+    for p in puntos {
+        wtr.serialize(p)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn guardar_ajustes_csv(ajustes: &[AjusteHolling], ruta: &str) -> csv::Result<()> {
+    let mut wtr = csv::Writer::from_path(ruta)?;
+    for a in ajustes {
+        wtr.serialize(a)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Respuesta del modelo con a y h conocidos y un ruido deterministico de +-2 %
+    fn puntos(modelo: ModeloHolling, a: f64, h: f64) -> Vec<PuntoRespuesta> {
+        (1..=12).map(|i| {
+            let densidad = 0.75 * i as f64;
+            let ruido = 1.0 + 0.02 * (i as f64 * 1.7).sin();
+            PuntoRespuesta {
+                presas: i * 10,
+                densidad: densidad as f32,
+                dias: 10,
+                capturas_por_depredador_dia: (modelo.evaluar(densidad, a, h) * ruido) as f32,
+                ataques_fallidos_por_depredador_dia: 0.0,
+            }
+        }).collect()
+    }
+
+    fn cerca(obtenido: f64, esperado: f64, tolerancia: f64) -> bool {
+        (obtenido - esperado).abs() <= tolerancia * esperado
+    }
+
+    #[test]
+    fn recupera_a_y_h_de_cada_tipo() {
+        for (modelo, a, h) in [(ModeloHolling::TipoI, 0.4, 0.0), (ModeloHolling::TipoII, 0.8, 0.25), (ModeloHolling::TipoIII, 0.3, 0.25)] {
+            let ajuste = ajustar_holling(modelo, &puntos(modelo, a, h));
+            assert!(cerca(ajuste.tasa_ataque, a, 0.1), "{}: a = {} frente a {}", ajuste.modelo, ajuste.tasa_ataque, a);
+            assert!(cerca(ajuste.tiempo_manipulacion, h, 0.1), "{}: h = {} frente a {}", ajuste.modelo, ajuste.tiempo_manipulacion, h);
+            assert!(ajuste.r_cuadrado > 0.99, "{}: R² = {}", ajuste.modelo, ajuste.r_cuadrado);
+        }
+    }
+
+    #[test]
+    fn elige_el_tipo_que_genero_los_datos() {
+        for (modelo, a, h) in [(ModeloHolling::TipoI, 0.4, 0.0), (ModeloHolling::TipoII, 0.8, 0.25), (ModeloHolling::TipoIII, 0.3, 0.25)] {
+            let datos = puntos(modelo, a, h);
+            let ajustes: Vec<_> = ModeloHolling::TODOS.iter().map(|&m| ajustar_holling(m, &datos)).collect();
+            assert_eq!(mejor_ajuste(&ajustes).unwrap().modelo, modelo.nombre(), "{:?}", ajustes);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use serde::Serialize;
+
+use crate::utilidades::ajuste::{minimizar, r_cuadrado, suma_cuadrados};
+use crate::utilidades::csv::EstadisticasDiarias;
+use crate::utilidades::edo::integrar_rk4;
+
+const SUB_PASOS_DIA: usize = 10;
+const ITERACIONES_AJUSTE: usize = 4000;
+const REINICIOS_AJUSTE: usize = 3;
+const ITERACIONES_EXPLORACION: usize = 300;
+const TASAS_INICIALES: [f64; 3] = [0.05, 0.2, 0.5];
+// Fuera de este rango una tasa (por dia) indica un ajuste degenerado: un termino del modelo se apago
+// o el optimizador se fue al infinito compensando dos parametros entre si
+const TASA_MINIMA: f64 = 1e-4;
+const TASA_MAXIMA: f64 = 50.0;
+const R_CUADRADO_MINIMO: f64 = 0.1;
+
+// ==================== MODELOS AGREGADOS ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModeloPoblacion { LotkaVolterra, RosenzweigMacArthur }
+
+impl ModeloPoblacion {
+    pub const TODOS: [ModeloPoblacion; 2] = [ModeloPoblacion::LotkaVolterra, ModeloPoblacion::RosenzweigMacArthur];
+
+    pub fn desde_nombre(nombre: &str) -> Option<ModeloPoblacion> {
+        match nombre.to_lowercase().as_str() {
+            "lv" | "lotka-volterra" => Some(ModeloPoblacion::LotkaVolterra),
+            "rm" | "rosenzweig-macarthur" => Some(ModeloPoblacion::RosenzweigMacArthur),
+            _ => None,
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            ModeloPoblacion::LotkaVolterra => "Lotka-Volterra",
+            ModeloPoblacion::RosenzweigMacArthur => "Rosenzweig-MacArthur",
+        }
+    }
+
+    pub fn abreviatura(&self) -> &'static str {
+        match self {
+            ModeloPoblacion::LotkaVolterra => "lv",
+            ModeloPoblacion::RosenzweigMacArthur => "rm",
+        }
+    }
+
+    // r: crecimiento de presas, k: capacidad de carga, a: tasa de ataque,
+    // h: tiempo de manipulacion, e: conversion presa -> depredador, m: mortalidad de depredadores
+    pub fn nombres_parametros(&self) -> &'static [&'static str] {
+        match self {
+            ModeloPoblacion::LotkaVolterra => &["r", "a", "e", "m"],
+            ModeloPoblacion::RosenzweigMacArthur => &["r", "k", "a", "h", "e", "m"],
+        }
+    }
+
+    /// dN/dt y dP/dt para y = [presas, depredadores]
+    pub fn derivada(&self, p: &[f64], y: &[f64]) -> Vec<f64> {
+        let (n, d) = (y[0], y[1]);
+        match self {
+            ModeloPoblacion::LotkaVolterra => {
+                let (r, a, e, m) = (p[0], p[1], p[2], p[3]);
+                vec![r * n - a * n * d, e * a * n * d - m * d]
+            }
+            ModeloPoblacion::RosenzweigMacArthur => {
+                let (r, k, a, h, e, m) = (p[0], p[1], p[2], p[3], p[4], p[5]);
+                let consumo = a * n / (1.0 + a * h * n);
+                vec![r * n * (1.0 - n / k) - consumo * d, e * consumo * d - m * d]
+            }
+        }
+    }
+
+    /// Equilibrio de coexistencia (N*, P*); None si no es positivo
+    pub fn equilibrio(&self, p: &[f64]) -> Option<(f64, f64)> {
+        let (n, d) = match self {
+            ModeloPoblacion::LotkaVolterra => {
+                let (r, a, e, m) = (p[0], p[1], p[2], p[3]);
+                (m / (e * a), r / a)
+            }
+            ModeloPoblacion::RosenzweigMacArthur => {
+                let (r, k, a, h, e, m) = (p[0], p[1], p[2], p[3], p[4], p[5]);
+                if e <= m * h {
+                    return None;
+                }
+                let n = m / (a * (e - m * h));
+                (n, r / a * (1.0 - n / k) * (1.0 + a * h * n))
+            }
+        };
+        (n > 0.0 && d > 0.0 && n.is_finite() && d.is_finite()).then_some((n, d))
+    }
+
+    // Parametros iniciales que ponen el equilibrio en la media observada
+    fn parametros_iniciales(&self, r: f64, m: f64, media_n: f64, media_d: f64, max_n: f64) -> Vec<f64> {
+        let a = r / media_d.max(0.1);
+        match self {
+            ModeloPoblacion::LotkaVolterra => vec![r, a, m / (a * media_n.max(0.1)), m],
+            ModeloPoblacion::RosenzweigMacArthur => {
+                let h = 0.1;
+                let e = m * (1.0 + a * h * media_n) / (a * media_n.max(0.1));
+                vec![r, 2.0 * max_n.max(1.0), a, h, e, m]
+            }
+        }
+    }
+}
+
+// ==================== AJUSTE ====================
+#[derive(Clone, Debug, Serialize)]
+pub struct PuntoAjuste {
+    pub dia: u32,
+    pub presas_observadas: f64,
+    pub presas_ajustadas: f64,
+    pub depredadores_observados: f64,
+    pub depredadores_ajustados: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct AjustePoblacion {
+    pub modelo: ModeloPoblacion,
+    pub parametros: Vec<f64>,
+    pub suma_cuadrados_presas: f64,
+    pub suma_cuadrados_depredadores: f64,
+    pub r_cuadrado_presas: f64,
+    pub r_cuadrado_depredadores: f64,
+    pub aic: f64,
+    pub equilibrio: Option<(f64, f64)>,
+    pub curvas: Vec<PuntoAjuste>,
+    pub avisos: Vec<String>, // vacio si el ajuste no parece degenerado
+}
+
+/// Ajusta el modelo a (conteo_total, depredadores_vivos) por minimos cuadrados;
+/// la condicion inicial es la primera observacion y cada serie se pondera por su varianza
+pub fn ajustar_poblacion(modelo: ModeloPoblacion, reportes: &[EstadisticasDiarias]) -> Result<AjustePoblacion, String> {
+    if reportes.len() < modelo.nombres_parametros().len() + 1 {
+        return Err(format!("se necesitan al menos {} dias para ajustar {}", modelo.nombres_parametros().len() + 1, modelo.nombre()));
+    }
+    let dia0 = reportes[0].dia;
+    let tiempos: Vec<usize> = reportes.iter().map(|r| r.dia.saturating_sub(dia0) as usize).collect();
+    let obs_n: Vec<f64> = reportes.iter().map(|r| r.conteo_total as f64).collect();
+    let obs_d: Vec<f64> = reportes.iter().map(|r| r.depredadores_vivos as f64).collect();
+    for (nombre, serie) in [("presas", &obs_n), ("depredadores", &obs_d)] {
+        if serie.iter().all(|&v| v == serie[0]) {
+            return Err(format!("la serie de {} no varia (siempre {}): no hay dinamica que ajustar", nombre, serie[0]));
+        }
+    }
+    let dias = *tiempos.iter().max().unwrap();
+    let y0 = [obs_n[0], obs_d[0]];
+
+    let media = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+    let varianza = |v: &[f64]| { let m = media(v); v.iter().map(|x| (x - m).powi(2)).sum::<f64>() / v.len() as f64 };
+    let (peso_n, peso_d) = (1.0 / varianza(&obs_n).max(1.0), 1.0 / varianza(&obs_d).max(1.0));
+
+    let simular = |p: &[f64]| -> (Vec<f64>, Vec<f64>) {
+        let tray = integrar_rk4(|y| modelo.derivada(p, y), &y0, dias, SUB_PASOS_DIA);
+        (tiempos.iter().map(|&t| tray[t][0]).collect(), tiempos.iter().map(|&t| tray[t][1]).collect())
+    };
+    let error = |log_p: &[f64]| -> f64 {
+        let p: Vec<f64> = log_p.iter().map(|v| v.exp()).collect();
+        let (n, d) = simular(&p);
+        let e = peso_n * suma_cuadrados(&obs_n, &n) + peso_d * suma_cuadrados(&obs_d, &d);
+        if e.is_finite() { e } else { f64::MAX }
+    };
+
+    // Exploracion corta desde varias tasas de crecimiento y mortalidad; se refina la mejor
+    let max_n = obs_n.iter().cloned().fold(0.0, f64::max);
+    let mut log_p = TASAS_INICIALES.iter()
+        .flat_map(|&r| TASAS_INICIALES.iter().map(move |&m| (r, m)))
+        .map(|(r, m)| {
+            let inicio: Vec<f64> = modelo.parametros_iniciales(r, m, media(&obs_n), media(&obs_d), max_n).iter().map(|v| v.ln()).collect();
+            minimizar(error, &inicio, &vec![0.5; inicio.len()], ITERACIONES_EXPLORACION)
+        })
+        .min_by(|a, b| error(a).total_cmp(&error(b)))
+        .unwrap();
+    // Reiniciar el simplex alrededor del mejor punto ayuda a no quedar en un minimo degenerado
+    for _ in 0..REINICIOS_AJUSTE {
+        let pasos = vec![0.5; log_p.len()];
+        log_p = minimizar(error, &log_p, &pasos, ITERACIONES_AJUSTE);
+    }
+    let parametros: Vec<f64> = log_p.iter().map(|v| v.exp()).collect();
+
+    let (aj_n, aj_d) = simular(&parametros);
+    let observaciones = (obs_n.len() + obs_d.len()) as f64;
+    let sce_ponderada = error(&log_p).max(1e-12);
+    let (r2_n, r2_d) = (r_cuadrado(&obs_n, &aj_n), r_cuadrado(&obs_d, &aj_d));
+    Ok(AjustePoblacion {
+        modelo,
+        suma_cuadrados_presas: suma_cuadrados(&obs_n, &aj_n),
+        suma_cuadrados_depredadores: suma_cuadrados(&obs_d, &aj_d),
+        r_cuadrado_presas: r2_n,
+        r_cuadrado_depredadores: r2_d,
+        aic: observaciones * (sce_ponderada / observaciones).ln() + 2.0 * parametros.len() as f64,
+        equilibrio: modelo.equilibrio(&parametros),
+        curvas: reportes.iter().enumerate().map(|(i, r)| PuntoAjuste {
+            dia: r.dia,
+            presas_observadas: obs_n[i],
+            presas_ajustadas: aj_n[i],
+            depredadores_observados: obs_d[i],
+            depredadores_ajustados: aj_d[i],
+        }).collect(),
+        avisos: avisos_ajuste(modelo, &parametros, r2_n, r2_d),
+        parametros,
+    })
+}
+
+// Tasas en el limite (la capacidad de carga k no es una tasa) y series que el modelo no explica
+fn avisos_ajuste(modelo: ModeloPoblacion, parametros: &[f64], r2_n: f64, r2_d: f64) -> Vec<String> {
+    let mut avisos = Vec::new();
+    for (nombre, &valor) in modelo.nombres_parametros().iter().zip(parametros) {
+        if *nombre != "k" && !(TASA_MINIMA..=TASA_MAXIMA).contains(&valor) {
+            avisos.push(format!("{} = {:.3e} esta fuera de [{}, {}]", nombre, valor, TASA_MINIMA, TASA_MAXIMA));
+        }
+    }
+    for (serie, r2) in [("presas", r2_n), ("depredadores", r2_d)] {
+        if r2 < R_CUADRADO_MINIMO {
+            avisos.push(format!("R² de {} = {:.3}: el modelo no explica la serie", serie, r2));
+        }
+    }
+    avisos
+}
+
+/// Curvas observadas y ajustadas, dia a dia
+pub fn guardar_ajuste_csv(ajuste: &AjustePoblacion, ruta: &str) -> csv::Result<()> {
+    let mut wtr = csv::Writer::from_path(ruta)?;
+    for p in &ajuste.curvas {
+        wtr.serialize(p)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Parametros, metricas de ajuste y equilibrio como pares (parametro, valor)
+pub fn guardar_parametros_csv(ajuste: &AjustePoblacion, ruta: &str) -> csv::Result<()> {
+    let mut wtr = csv::Writer::from_path(ruta)?;
+    wtr.write_record(["parametro", "valor"])?;
+    let mut filas: Vec<(&str, f64)> = ajuste.modelo.nombres_parametros().iter().copied().zip(ajuste.parametros.iter().copied()).collect();
+    filas.push(("sce_presas", ajuste.suma_cuadrados_presas));
+    filas.push(("sce_depredadores", ajuste.suma_cuadrados_depredadores));
+    filas.push(("r2_presas", ajuste.r_cuadrado_presas));
+    filas.push(("r2_depredadores", ajuste.r_cuadrado_depredadores));
+    filas.push(("aic", ajuste.aic));
+    filas.push(("degenerado", if ajuste.avisos.is_empty() { 0.0 } else { 1.0 }));
+    if let Some((n, d)) = ajuste.equilibrio {
+        filas.push(("equilibrio_presas", n));
+        filas.push(("equilibrio_depredadores", d));
+    }
+    for (nombre, valor) in filas {
+        wtr.write_record([nombre.to_string(), valor.to_string()])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Serie sin ruido generada con el mismo modelo (conteos redondeados como en reportes.csv)
+    fn serie(modelo: ModeloPoblacion, parametros: &[f64], y0: [f64; 2], dias: usize) -> Vec<EstadisticasDiarias> {
+        integrar_rk4(|y| modelo.derivada(parametros, y), &y0, dias, SUB_PASOS_DIA).iter().enumerate()
+            .map(|(dia, y)| EstadisticasDiarias {
+                dia: dia as u32,
+                conteo_total: y[0].round() as usize,
+                depredadores_vivos: y[1].round() as usize,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn cerca(obtenido: f64, esperado: f64, tolerancia: f64) -> bool {
+        (obtenido - esperado).abs() <= tolerancia * esperado
+    }
+
+    #[test]
+    fn recupera_parametros_de_lotka_volterra() {
+        let reales = [0.5, 0.002, 0.5, 0.3];
+        let reportes = serie(ModeloPoblacion::LotkaVolterra, &reales, [400.0, 200.0], 60);
+        let ajuste = ajustar_poblacion(ModeloPoblacion::LotkaVolterra, &reportes).unwrap();
+        for (p, r) in ajuste.parametros.iter().zip(reales) {
+            assert!(cerca(*p, r, 0.05), "{:?} frente a {:?}", ajuste.parametros, reales);
+        }
+        assert!(ajuste.r_cuadrado_presas > 0.99 && ajuste.r_cuadrado_depredadores > 0.99);
+        assert!(ajuste.avisos.is_empty(), "{:?}", ajuste.avisos);
+    }
+
+    #[test]
+    fn recupera_la_dinamica_de_rosenzweig_macarthur() {
+        let reales = [0.8, 1000.0, 0.002, 0.1, 0.5, 0.25];
+        let reportes = serie(ModeloPoblacion::RosenzweigMacArthur, &reales, [500.0, 100.0], 80);
+        let ajuste = ajustar_poblacion(ModeloPoblacion::RosenzweigMacArthur, &reportes).unwrap();
+        assert!(ajuste.r_cuadrado_presas > 0.98 && ajuste.r_cuadrado_depredadores > 0.98,
+                "R² {} {}", ajuste.r_cuadrado_presas, ajuste.r_cuadrado_depredadores);
+        // r y m se identifican bien; el equilibrio tambien aunque k y h se compensen entre si
+        assert!(cerca(ajuste.parametros[0], 0.8, 0.15) && cerca(ajuste.parametros[5], 0.25, 0.15), "{:?}", ajuste.parametros);
+        let (n, d) = ajuste.equilibrio.unwrap();
+        let (n_real, d_real) = ModeloPoblacion::RosenzweigMacArthur.equilibrio(&reales).unwrap();
+        assert!(cerca(n, n_real, 0.1) && cerca(d, d_real, 0.1), "({}, {}) frente a ({}, {})", n, d, n_real, d_real);
+    }
+
+    #[test]
+    fn rechaza_una_serie_de_depredadores_constante() {
+        let mut reportes = serie(ModeloPoblacion::LotkaVolterra, &[0.5, 0.002, 0.5, 0.3], [400.0, 200.0], 30);
+        for r in reportes.iter_mut() {
+            r.depredadores_vivos = 1;
+        }
+        assert!(ajustar_poblacion(ModeloPoblacion::LotkaVolterra, &reportes).is_err());
+    }
+
+    #[test]
+    fn avisa_de_tasas_en_el_limite() {
+        let avisos = avisos_ajuste(ModeloPoblacion::LotkaVolterra, &[459.8, 459.8, 1e-9, 1e-9], 0.76, 0.0);
+        assert_eq!(avisos.len(), 5, "{:?}", avisos);
+        assert!(avisos_ajuste(ModeloPoblacion::LotkaVolterra, &[0.5, 0.002, 0.5, 0.3], 0.99, 0.99).is_empty());
+    }
+}
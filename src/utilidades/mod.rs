@@ -1,6 +1,16 @@
 pub mod csv;
 pub mod configuraciones;
 pub mod reportes;
+pub mod plano_fase;
+pub mod ajuste;
+pub mod holling;
+pub mod edo;
+pub mod lotka_volterra;
+pub mod analisis;
+pub mod salidas;
+pub mod densidad;
+pub mod grabacion;
+pub mod renderizado;
 
 pub use csv::EstadisticasDiarias;
 pub use configuraciones::*;
@@ -0,0 +1,132 @@
+use std::fmt::Write as _;
+use std::fs;
+
+use crate::entidades::Especie;
+use crate::utilidades::csv::EstadisticasDiarias;
+
+// ==================== EJES DEL PLANO DE FASE ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EjePresas { Total, Especie(Especie) }
+
+impl EjePresas {
+    // Total -> Conejo -> Raton -> Ardilla -> Total
+    pub fn siguiente(&self) -> EjePresas {
+        match self {
+            EjePresas::Total => EjePresas::Especie(Especie::Conejo),
+            EjePresas::Especie(Especie::Conejo) => EjePresas::Especie(Especie::Raton),
+            EjePresas::Especie(Especie::Raton) => EjePresas::Especie(Especie::Ardilla),
+            EjePresas::Especie(Especie::Ardilla) => EjePresas::Total,
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            EjePresas::Total => "Presas totales",
+            EjePresas::Especie(e) => e.nombre(),
+        }
+    }
+
+    pub fn valor(&self, r: &EstadisticasDiarias) -> f32 {
+        match self {
+            EjePresas::Total => r.conteo_total as f32,
+            EjePresas::Especie(Especie::Conejo) => r.conteo_conejos as f32,
+            EjePresas::Especie(Especie::Raton) => r.conteo_ratones as f32,
+            EjePresas::Especie(Especie::Ardilla) => r.conteo_ardillas as f32,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EjeDepredadores { Conteo, Reserva }
+
+impl EjeDepredadores {
+    pub fn siguiente(&self) -> EjeDepredadores {
+        match self {
+            EjeDepredadores::Conteo => EjeDepredadores::Reserva,
+            EjeDepredadores::Reserva => EjeDepredadores::Conteo,
+        }
+    }
+
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            EjeDepredadores::Conteo => "Depredadores vivos",
+            EjeDepredadores::Reserva => "Reserva total depredadores",
+        }
+    }
+
+    pub fn valor(&self, r: &EstadisticasDiarias) -> f32 {
+        match self {
+            EjeDepredadores::Conteo => r.depredadores_vivos as f32,
+            EjeDepredadores::Reserva => r.reserva_total_depredadores,
+        }
+    }
+}
+
+/// Trayectoria (presas, depredadores) dia a dia, en el orden de los reportes
+pub fn puntos_plano_fase(reportes: &[EstadisticasDiarias], eje_x: EjePresas, eje_y: EjeDepredadores) -> Vec<(f32, f32)> {
+    reportes.iter().map(|r| (eje_x.valor(r), eje_y.valor(r))).collect()
+}
+
+/// Maximo de cada eje, nunca menor a 1 para no dividir por cero al escalar
+pub fn limites_plano_fase(puntos: &[(f32, f32)]) -> (f32, f32) {
+    puntos.iter().fold((1.0f32, 1.0f32), |(mx, my), &(x, y)| (mx.max(x), my.max(y)))
+}
+
+/// Escribe la trayectoria como SVG; no necesita ventana, sirve tambien sin interfaz
+pub fn guardar_plano_fase_svg(
+    reportes: &[EstadisticasDiarias],
+    eje_x: EjePresas,
+    eje_y: EjeDepredadores,
+    ruta: &str,
+) -> std::io::Result<()> {
+    const ANCHO: f32 = 640.0;
+    const ALTO: f32 = 480.0;
+    const MARGEN: f32 = 60.0;
+
+    let puntos = puntos_plano_fase(reportes, eje_x, eje_y);
+    let (max_x, max_y) = limites_plano_fase(&puntos);
+    let escalar = |(x, y): (f32, f32)| -> (f32, f32) {
+        (
+            MARGEN + x / max_x * (ANCHO - 2.0 * MARGEN),
+            ALTO - MARGEN - y / max_y * (ALTO - 2.0 * MARGEN),
+        )
+    };
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{ANCHO}" height="{ALTO}" viewBox="0 0 {ANCHO} {ALTO}">"#);
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+    // Ejes
+    let _ = writeln!(
+        svg,
+        r#"<path d="M{m} {m} L{m} {b} L{d} {b}" fill="none" stroke="black"/>"#,
+        m = MARGEN, b = ALTO - MARGEN, d = ANCHO - MARGEN
+    );
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle" font-size="14">{}</text>"#, ANCHO / 2.0, ALTO - 20.0, eje_x.nombre());
+    let _ = writeln!(
+        svg,
+        r#"<text x="20" y="{y}" text-anchor="middle" font-size="14" transform="rotate(-90 20 {y})">{}</text>"#,
+        eje_y.nombre(), y = ALTO / 2.0
+    );
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="end" font-size="12">{:.0}</text>"#, ANCHO - MARGEN, ALTO - MARGEN + 16.0, max_x);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="end" font-size="12">{:.0}</text>"#, MARGEN - 4.0, MARGEN + 4.0, max_y);
+
+    // Trayectoria
+    if !puntos.is_empty() {
+        let linea: Vec<String> = puntos.iter()
+            .map(|&p| {
+                let (x, y) = escalar(p);
+                format!("{x:.1},{y:.1}")
+            })
+            .collect();
+        let _ = writeln!(svg, r#"<polyline points="{}" fill="none" stroke="steelblue" stroke-width="1.5"/>"#, linea.join(" "));
+
+        let (x0, y0) = escalar(puntos[0]);
+        let (xn, yn) = escalar(puntos[puntos.len() - 1]);
+        let _ = writeln!(svg, r#"<circle cx="{x0:.1}" cy="{y0:.1}" r="4" fill="green"/>"#);
+        let _ = writeln!(svg, r#"<circle cx="{xn:.1}" cy="{yn:.1}" r="4" fill="red"/>"#);
+    }
+
+    svg.push_str("</svg>\n");
+    fs::write(ruta, svg)
+}
@@ -0,0 +1,261 @@
+use std::fmt::Write as _;
+use std::fs;
+
+use crate::entidades::{Mundo, Terreno, TipoCelda, Especie, EspecieDepredador, EstadoSalud, Sexo};
+use crate::sistemas::ciclo_diario::oscuridad;
+use crate::utilidades::configuraciones::{ANCHO_MUNDO, ALTO_MUNDO, PALETA_TERRENO};
+use crate::utilidades::grabacion::Lienzo;
+
+// ==================== COLORES ====================
+/// Color de 8 bits por canal; cada destino (ventana, terminal, imagen, SVG) lo pasa a su formato
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    /// Transparencia entre 0 y 1
+    pub fn opacidad(&self) -> f32 {
+        self.a as f32 / 255.0
+    }
+}
+
+pub const GRIS_CLARO: Rgba = Rgba::rgb(199, 199, 199);
+pub const NEGRO: Rgba = Rgba::rgb(0, 0, 0);
+pub const ROJO: Rgba = Rgba::rgb(230, 41, 56);
+pub const AZUL: Rgba = Rgba::rgb(0, 120, 242);
+pub const VERDE: Rgba = Rgba::rgb(0, 227, 48);
+pub const NARANJA: Rgba = Rgba::rgb(255, 161, 0);
+
+// Las entidades no saben como se dibujan: cada renderer toma los colores de aca
+pub fn color_especie(especie: Especie) -> Rgba {
+    match especie {
+        Especie::Conejo => AZUL,
+        Especie::Raton => VERDE,
+        Especie::Ardilla => NARANJA,
+    }
+}
+
+pub fn color_celda(tipo: TipoCelda) -> Rgba {
+    let [r, g, b] = PALETA_TERRENO.iter().find(|(t, _)| *t == tipo).map(|(_, c)| *c).unwrap();
+    Rgba::rgb(r, g, b)
+}
+
+// ==================== VISTA DE SOLO LECTURA ====================
+#[derive(Clone, Copy, Debug)]
+pub enum ClaseVista {
+    Presa { especie: Especie, sexo: Sexo },
+    Depredador { especie: EspecieDepredador },
+}
+
+/// Lo que un renderer ve de un organismo: una copia, asi no puede modificar la simulacion
+#[derive(Clone, Copy, Debug)]
+pub struct VistaOrganismo {
+    pub x: f32,
+    pub y: f32,
+    pub r: f32,
+    pub clase: ClaseVista,
+    pub enfermo: bool,
+    pub espera: f32,
+}
+
+impl VistaOrganismo {
+    /// None si la fila no tiene cuerpo que dibujar o no es presa ni depredador
+    pub fn de(mundo: &Mundo, fila: usize) -> Option<Self> {
+        let clase = match (mundo.especies.get(fila), mundo.especies_depredador.get(fila)) {
+            (Some(&especie), _) => ClaseVista::Presa { especie, sexo: mundo.reproducciones.get(fila)?.sexo },
+            (None, Some(&especie)) => ClaseVista::Depredador { especie },
+            (None, None) => return None,
+        };
+        let pos = mundo.posiciones.get(fila)?;
+        Some(Self {
+            x: pos.x,
+            y: pos.y,
+            r: mundo.cuerpos.get(fila)?.radio,
+            clase,
+            enfermo: mundo.salud.get(fila).is_some_and(|s| s.estado == EstadoSalud::Enfermo),
+            espera: mundo.esperas.get(fila).map_or(0.0, |e| e.restante),
+        })
+    }
+
+    // Relleno y color del anillo de enfermedad
+    pub fn colores(&self) -> (Rgba, Rgba) {
+        match self.clase {
+            ClaseVista::Presa { especie, .. } => (color_especie(especie), ROJO),
+            ClaseVista::Depredador { .. } => (ROJO, NEGRO),
+        }
+    }
+}
+
+/// Organismos vivos, primero las presas y encima los depredadores
+pub fn vistas(mundo: &Mundo) -> impl Iterator<Item = VistaOrganismo> + '_ {
+    mundo.especies.filas().chain(mundo.especies_depredador.filas())
+        .filter(|&i| mundo.esta_vivo(i))
+        .filter_map(|i| VistaOrganismo::de(mundo, i))
+}
+
+// ==================== RENDERER ====================
+// Dibuja el mundo en coordenadas del mundo; cada destino (ventana, imagen, SVG) implementa las tres partes
+pub trait Renderer {
+    fn terreno(&mut self, terreno: &Terreno);
+    fn organismo(&mut self, vista: &VistaOrganismo);
+    fn noche(&mut self, tiempo_del_dia: f32);
+}
+
+/// No dibuja nada: recorre el mundo igual que los demas, para medir la simulacion sin destino
+pub struct RendererNulo;
+
+impl Renderer for RendererNulo {
+    fn terreno(&mut self, _terreno: &Terreno) {}
+    fn organismo(&mut self, _vista: &VistaOrganismo) {}
+    fn noche(&mut self, _tiempo_del_dia: f32) {}
+}
+
+/// Terreno, organismos y noche en ese orden
+pub fn dibujar_mundo(renderer: &mut impl Renderer, terreno: &Terreno, mundo: &Mundo, tiempo_del_dia: f32) {
+    renderer.terreno(terreno);
+    for v in vistas(mundo) {
+        renderer.organismo(&v);
+    }
+    renderer.noche(tiempo_del_dia);
+}
+
+/// Color y opacidad de la noche, o None de dia
+pub fn color_noche(tiempo_del_dia: f32) -> Option<Rgba> {
+    let alfa = (0.45 * oscuridad(tiempo_del_dia) * 255.0).round() as u8;
+    (alfa > 0).then_some(Rgba { r: 0, g: 0, b: 38, a: alfa })
+}
+
+// ==================== IMAGEN (SIN VENTANA) ====================
+// Rasterizador por software sobre un Lienzo, a `escala` pixeles por unidad del mundo (sin textos)
+pub struct RendererLienzo {
+    pub lienzo: Lienzo,
+    escala: f32,
+}
+
+impl RendererLienzo {
+    pub fn new(escala: f32) -> Self {
+        let ancho = (ANCHO_MUNDO * escala).round().max(1.0) as usize;
+        let alto = (ALTO_MUNDO * escala).round().max(1.0) as usize;
+        Self { lienzo: Lienzo::new(ancho, alto, GRIS_CLARO), escala }
+    }
+}
+
+impl Renderer for RendererLienzo {
+    fn terreno(&mut self, terreno: &Terreno) {
+        let e = self.escala;
+        for py in 0..self.lienzo.alto {
+            for px in 0..self.lienzo.ancho {
+                let tipo = terreno.celda_en((px as f32 + 0.5) / e, (py as f32 + 0.5) / e);
+                if tipo != TipoCelda::Campo {
+                    self.lienzo.pintar(px, py, color_celda(tipo));
+                }
+            }
+        }
+    }
+
+    fn organismo(&mut self, v: &VistaOrganismo) {
+        let e = self.escala;
+        let (relleno, anillo) = v.colores();
+        self.lienzo.circulo(v.x * e, v.y * e, v.r * e, None, relleno);
+        if v.enfermo {
+            self.lienzo.circulo(v.x * e, v.y * e, (v.r + 2.0) * e, Some(2.0 * e), anillo);
+        }
+    }
+
+    fn noche(&mut self, tiempo_del_dia: f32) {
+        if let Some(color) = color_noche(tiempo_del_dia) {
+            let (ancho, alto) = (self.lienzo.ancho as f32, self.lienzo.alto as f32);
+            self.lienzo.rectangulo(0.0, 0.0, ancho, alto, color);
+        }
+    }
+}
+
+/// Dibuja el mundo como imagen, a escala del mundo
+pub fn rasterizar_mundo(terreno: &Terreno, mundo: &Mundo, tiempo_del_dia: f32, escala: f32) -> Lienzo {
+    let mut renderer = RendererLienzo::new(escala);
+    dibujar_mundo(&mut renderer, terreno, mundo, tiempo_del_dia);
+    renderer.lienzo
+}
+
+// ==================== SVG ====================
+// Acumula los elementos en un documento SVG del tamaño del mundo
+pub struct RendererSvg {
+    cuerpo: String,
+}
+
+fn color_svg(c: Rgba) -> String {
+    format!("rgb({},{},{})", c.r, c.g, c.b)
+}
+
+impl Default for RendererSvg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RendererSvg {
+    pub fn new() -> Self {
+        Self { cuerpo: String::new() }
+    }
+
+    pub fn guardar(&self, ruta: &str) -> std::io::Result<()> {
+        let documento = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+             <rect width=\"{w}\" height=\"{h}\" fill=\"{fondo}\"/>\n{cuerpo}</svg>\n",
+            w = ANCHO_MUNDO, h = ALTO_MUNDO, fondo = color_svg(GRIS_CLARO), cuerpo = self.cuerpo,
+        );
+        fs::write(ruta, documento)
+    }
+}
+
+impl Renderer for RendererSvg {
+    // Un rectangulo por celda que no sea campo abierto
+    fn terreno(&mut self, terreno: &Terreno) {
+        let ancho = ANCHO_MUNDO / terreno.columnas() as f32;
+        let alto = ALTO_MUNDO / terreno.filas() as f32;
+        for f in 0..terreno.filas() {
+            for c in 0..terreno.columnas() {
+                let tipo = terreno.celda(c, f);
+                if tipo != TipoCelda::Campo {
+                    let color = color_celda(tipo);
+                    let _ = writeln!(self.cuerpo, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>",
+                                     c as f32 * ancho, f as f32 * alto, ancho, alto, color_svg(color), color.opacidad());
+                }
+            }
+        }
+    }
+
+    fn organismo(&mut self, v: &VistaOrganismo) {
+        let (relleno, anillo) = v.colores();
+        // El nombre de la especie queda como titulo (se ve al pasar el raton)
+        let nombre = match v.clase {
+            ClaseVista::Presa { especie, .. } => especie.nombre(),
+            ClaseVista::Depredador { especie } => especie.nombre(),
+        };
+        let _ = writeln!(self.cuerpo, "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\"><title>{}</title></circle>",
+                         v.x, v.y, v.r, color_svg(relleno), nombre);
+        if v.enfermo {
+            let _ = writeln!(self.cuerpo, "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                             v.x, v.y, v.r + 2.0, color_svg(anillo));
+        }
+        if let ClaseVista::Presa { sexo, .. } = v.clase {
+            let letra = if sexo == Sexo::Macho { "M" } else { "H" };
+            let _ = writeln!(self.cuerpo, "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\">{}</text>", v.x - 5.0, v.y - 12.0, letra);
+        }
+    }
+
+    fn noche(&mut self, tiempo_del_dia: f32) {
+        if let Some(color) = color_noche(tiempo_del_dia) {
+            let _ = writeln!(self.cuerpo, "<rect width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>",
+                             ANCHO_MUNDO, ALTO_MUNDO, color_svg(color), color.opacidad());
+        }
+    }
+}
@@ -1,46 +1,211 @@
-use crate::entidades::organismo::Organismo;
-use crate::entidades::{Presa, Depredador, Especie, EstadoSalud};
+use serde::Serialize;
+
+use crate::entidades::{Mundo, Especie, EstadoSalud, EstrategiaCaza, Sexo};
+use crate::utilidades::configuraciones::ANCHO_CLASE_EDAD;
 use crate::utilidades::csv::EstadisticasDiarias;
+use crate::sistemas::diario::ContadoresDiarios;
+use crate::sistemas::estaciones::Estacion;
+
+// ==================== ESTADISTICAS POR ESPECIE ====================
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EstadisticasEspecie {
+    pub conteo: usize,
+    pub proporcion_machos: f32,
+    pub edad_media: f32,
+    pub edad_mediana: f32,
+    pub peso_medio: f32,
+    pub peso_mediano: f32,
+    pub en_reproduccion: usize,
+    pub enfermos: usize,
+}
+
+fn mediana(mut valores: Vec<f32>) -> f32 {
+    if valores.is_empty() {
+        return 0.0;
+    }
+    valores.sort_by(f32::total_cmp);
+    let m = valores.len() / 2;
+    if valores.len().is_multiple_of(2) { (valores[m - 1] + valores[m]) / 2.0 } else { valores[m] }
+}
 
-/// Compila un reporte diario a partir del estado actual de presas y depredadores
-pub fn compilar_reporte_diario(
-    presas: &[Presa],
-    depredadores: &[Depredador],
-    muertes_pred: u32,
-    muertes_enf: u32,
-    nuevos_infectados: u32,
-    recuperaciones: u32,
-    reproducciones: u32,
-    dia: u32,
-) -> EstadisticasDiarias {
-    let mut conteo = [0usize; 3];
-
-    for p in presas {
-        if p.esta_vivo() {
-            match p.especie() {
-                Especie::Conejo => conteo[0] += 1,
-                Especie::Raton => conteo[1] += 1,
-                Especie::Ardilla => conteo[2] += 1,
-            }
+impl EstadisticasEspecie {
+    /// Resumen de las presas vivas de una especie (todo en cero si no queda ninguna)
+    pub fn de(mundo: &Mundo, especie: Especie) -> Self {
+        let vivas = presas_vivas(mundo, especie);
+        if vivas.is_empty() {
+            return Self::default();
+        }
+        let n = vivas.len() as f32;
+        let edades: Vec<f32> = vivas.iter().map(|&i| mundo.crecimientos[i].edad as f32).collect();
+        let pesos: Vec<f32> = vivas.iter().map(|&i| mundo.crecimientos[i].peso).collect();
+        Self {
+            conteo: vivas.len(),
+            proporcion_machos: vivas.iter().filter(|&&i| mundo.reproducciones[i].sexo == Sexo::Macho).count() as f32 / n,
+            edad_media: edades.iter().sum::<f32>() / n,
+            edad_mediana: mediana(edades),
+            peso_medio: pesos.iter().sum::<f32>() / n,
+            peso_mediano: mediana(pesos),
+            en_reproduccion: vivas.iter().filter(|&&i| mundo.reproducciones[i].activa).count(),
+            enfermos: vivas.iter().filter(|&&i| mundo.salud[i].estado == EstadoSalud::Enfermo).count(),
         }
     }
+}
+
+// Filas de las presas vivas de una especie
+fn presas_vivas(mundo: &Mundo, especie: Especie) -> Vec<usize> {
+    mundo.especies.iter()
+        .filter(|&(i, &e)| mundo.esta_vivo(i) && e == especie)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Compila un reporte diario a partir del estado actual del mundo y de lo contado en el dia;
+/// los depredadores son las filas con Caza
+pub fn compilar_reporte_diario(mundo: &Mundo, contadores: &ContadoresDiarios, dia: u32) -> EstadisticasDiarias {
+    let capturas_por_estrategia = contadores.capturas_por_estrategia;
+    let nacimientos_por_especie = contadores.nacimientos_por_especie;
+    let [conejos, ratones, ardillas] = Especie::TODAS.map(|e| EstadisticasEspecie::de(mundo, e));
 
+    let depredadores: Vec<usize> = mundo.cazas.filas().filter(|&i| mundo.esta_vivo(i)).collect();
     let dep_enfermos = depredadores.iter()
-        .filter(|d| d.esta_vivo() && d.salud() == EstadoSalud::Enfermo)
+        .filter(|&&i| mundo.salud[i].estado == EstadoSalud::Enfermo)
         .count();
 
     EstadisticasDiarias {
         dia,
-        conteo_conejos: conteo[0],
-        conteo_ratones: conteo[1],
-        conteo_ardillas: conteo[2],
-        conteo_total: conteo.iter().sum(),
-        muertes_por_predacion: muertes_pred,
-        muertes_por_enfermedad: muertes_enf,
-        nuevos_infectados,
-        recuperaciones,
-        reproducciones,
+        estacion: Estacion::del_dia(dia).nombre().to_string(),
+        conteo_conejos: conejos.conteo,
+        conteo_ratones: ratones.conteo,
+        conteo_ardillas: ardillas.conteo,
+        conteo_total: conejos.conteo + ratones.conteo + ardillas.conteo,
+        muertes_por_predacion: contadores.muertes_por_predacion,
+        muertes_por_enfermedad: contadores.muertes_por_enfermedad,
+        muertes_por_eventos: contadores.muertes_por_eventos,
+        nuevos_infectados: contadores.nuevos_infectados,
+        recuperaciones: contadores.recuperaciones,
+        reproducciones: contadores.reproducciones,
+        emigraciones: contadores.emigraciones,
         depredadores_enfermos: dep_enfermos,
-        depredadores_vivos: depredadores.iter().filter(|d| d.esta_vivo()).count(),
+        depredadores_vivos: depredadores.len(),
+        reserva_total_depredadores: depredadores.iter()
+            .map(|&i| mundo.dietas[i].reserva)
+            .sum(),
+        capturas_mas_pesada: capturas_por_estrategia[EstrategiaCaza::MasPesada.indice()],
+        capturas_mas_cercana: capturas_por_estrategia[EstrategiaCaza::MasCercana.indice()],
+        capturas_forrajeo_optimo: capturas_por_estrategia[EstrategiaCaza::ForrajeoOptimo.indice()],
+        capturas_emboscada: capturas_por_estrategia[EstrategiaCaza::Emboscada.indice()],
+        capturas_persecucion: capturas_por_estrategia[EstrategiaCaza::Persecucion.indice()],
+        ataques_fallidos: contadores.ataques_fallidos,
+        proporcion_machos_conejos: conejos.proporcion_machos,
+        edad_media_conejos: conejos.edad_media,
+        edad_mediana_conejos: conejos.edad_mediana,
+        peso_medio_conejos: conejos.peso_medio,
+        peso_mediano_conejos: conejos.peso_mediano,
+        en_reproduccion_conejos: conejos.en_reproduccion,
+        enfermos_conejos: conejos.enfermos,
+        nacimientos_conejos: nacimientos_por_especie[0],
+        proporcion_machos_ratones: ratones.proporcion_machos,
+        edad_media_ratones: ratones.edad_media,
+        edad_mediana_ratones: ratones.edad_mediana,
+        peso_medio_ratones: ratones.peso_medio,
+        peso_mediano_ratones: ratones.peso_mediano,
+        en_reproduccion_ratones: ratones.en_reproduccion,
+        enfermos_ratones: ratones.enfermos,
+        nacimientos_ratones: nacimientos_por_especie[1],
+        proporcion_machos_ardillas: ardillas.proporcion_machos,
+        edad_media_ardillas: ardillas.edad_media,
+        edad_mediana_ardillas: ardillas.edad_mediana,
+        peso_medio_ardillas: ardillas.peso_medio,
+        peso_mediano_ardillas: ardillas.peso_mediano,
+        en_reproduccion_ardillas: ardillas.en_reproduccion,
+        enfermos_ardillas: ardillas.enfermos,
+        nacimientos_ardillas: nacimientos_por_especie[2],
+    }
+}
+
+// ==================== PIRAMIDE DE EDADES ====================
+/// Una clase de edad de una especie en un dia: [edad_desde, edad_hasta) dias
+#[derive(Clone, Debug, Serialize)]
+pub struct ClasePiramide {
+    pub dia: u32,
+    pub especie: &'static str,
+    pub edad_desde: u32,
+    pub edad_hasta: u32,
+    pub machos: usize,
+    pub hembras: usize,
+}
+
+/// Histograma de edades por especie y sexo en clases de ANCHO_CLASE_EDAD dias,
+/// desde cero hasta la clase de la presa mas vieja
+pub fn compilar_piramide_edades(mundo: &Mundo, dia: u32) -> Vec<ClasePiramide> {
+    let mut clases = Vec::new();
+    for especie in Especie::TODAS {
+        let vivas: Vec<(Sexo, u32)> = presas_vivas(mundo, especie).into_iter()
+            .map(|i| (mundo.reproducciones[i].sexo, mundo.crecimientos[i].edad))
+            .collect();
+        let Some(edad_maxima) = vivas.iter().map(|&(_, edad)| edad).max() else { continue };
+        for k in 0..=edad_maxima / ANCHO_CLASE_EDAD {
+            let (desde, hasta) = (k * ANCHO_CLASE_EDAD, (k + 1) * ANCHO_CLASE_EDAD);
+            let en_clase = |sexo: Sexo| vivas.iter()
+                .filter(|&&(s, edad)| s == sexo && edad >= desde && edad < hasta)
+                .count();
+            clases.push(ClasePiramide {
+                dia,
+                especie: especie.nombre(),
+                edad_desde: desde,
+                edad_hasta: hasta,
+                machos: en_clase(Sexo::Macho),
+                hembras: en_clase(Sexo::Hembra),
+            });
+        }
+    }
+    clases
+}
+
+// ==================== BITACORA DE DEPREDADORES ====================
+/// Un dia de un depredador: reserva al empezar y al terminar, capturas, ingesta, recorrido y salud
+#[derive(Clone, Debug, Serialize)]
+pub struct RegistroDepredador {
+    pub dia: u32,
+    pub id: u32,
+    pub especie: &'static str,
+    pub estrategia: &'static str,
+    pub reserva_inicio: f32,
+    pub reserva_fin: f32,
+    pub capturas_conejos: u32,
+    pub capturas_ratones: u32,
+    pub capturas_ardillas: u32,
+    pub biomasa_ingerida: f32,
+    pub distancia_recorrida: f32,
+    pub dias_enfermo: u32,
+    pub salud: &'static str,
+    pub vivo: bool,
+}
+
+/// Una fila por entidad con Bitacora viva o muerta en el dia, y empieza el dia siguiente de cada una
+pub fn compilar_bitacora_depredadores(mundo: &mut Mundo, dia: u32) -> Vec<RegistroDepredador> {
+    let mut registros = Vec::new();
+    for (i, b) in mundo.bitacoras.iter_mut() {
+        let (salud, reserva, vivo) = (mundo.salud[i], mundo.dietas[i].reserva, mundo.vivos[i]);
+        if b.activa {
+            registros.push(RegistroDepredador {
+                dia,
+                id: mundo.ids[i],
+                especie: mundo.especies_depredador[i].nombre(),
+                estrategia: mundo.cazas[i].estrategia.nombre(),
+                reserva_inicio: b.reserva_inicio_dia,
+                reserva_fin: reserva,
+                capturas_conejos: b.capturas_dia[0],
+                capturas_ratones: b.capturas_dia[1],
+                capturas_ardillas: b.capturas_dia[2],
+                biomasa_ingerida: b.biomasa_dia,
+                distancia_recorrida: b.distancia_dia,
+                dias_enfermo: salud.dias_enfermo,
+                salud: salud.estado.nombre(),
+                vivo,
+            });
+        }
+        b.iniciar_dia(reserva, vivo);
     }
+    registros
 }
@@ -0,0 +1,654 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::sistemas::eventos::RegistroEvento;
+use crate::utilidades::configuraciones::{RUTA_REPORTES, RUTA_EVENTOS, RUTA_BITACORA, RUTA_PIRAMIDE, RUTA_BASE_DATOS, RUTA_TERRENO, RUTA_GUION_EVENTOS, RUTA_ESCENARIO};
+use crate::utilidades::csv::EstadisticasDiarias;
+use crate::utilidades::reportes::{ClasePiramide, RegistroDepredador};
+
+// ==================== FORMATOS ====================
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FormatoReportes { Csv, Json, JsonLineas, Sqlite }
+
+impl FormatoReportes {
+    pub fn desde_nombre(nombre: &str) -> Option<FormatoReportes> {
+        match nombre.to_lowercase().as_str() {
+            "csv" => Some(FormatoReportes::Csv),
+            "json" => Some(FormatoReportes::Json),
+            "jsonl" => Some(FormatoReportes::JsonLineas),
+            "sqlite" => Some(FormatoReportes::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Archivos que escribe el formato
+    pub fn rutas(&self) -> Vec<String> {
+        match self {
+            FormatoReportes::Csv => vec![format!("{}.csv", RUTA_REPORTES), format!("{}.csv", RUTA_EVENTOS), format!("{}.csv", RUTA_BITACORA)],
+            FormatoReportes::Json => vec![format!("{}.json", RUTA_REPORTES)],
+            FormatoReportes::JsonLineas => vec![format!("{}.jsonl", RUTA_REPORTES), format!("{}.jsonl", RUTA_EVENTOS), format!("{}.jsonl", RUTA_BITACORA)],
+            FormatoReportes::Sqlite => vec![RUTA_BASE_DATOS.to_string()],
+        }
+    }
+
+    /// Donde queda la piramide de edades cuando se registra
+    pub fn ruta_piramide(&self) -> String {
+        match self {
+            FormatoReportes::Csv => format!("{}.csv", RUTA_PIRAMIDE),
+            FormatoReportes::Json => format!("{}.json (clave {})", RUTA_REPORTES, RUTA_PIRAMIDE),
+            FormatoReportes::JsonLineas => format!("{}.jsonl", RUTA_PIRAMIDE),
+            FormatoReportes::Sqlite => format!("{} (tabla {})", RUTA_BASE_DATOS, RUTA_PIRAMIDE),
+        }
+    }
+}
+
+/// Datos que identifican una corrida; van como encabezado de cada salida
+#[derive(Clone, Debug, Serialize)]
+pub struct MetadatosCorrida {
+    pub semilla: u64,
+    pub version: &'static str,
+    pub hash_configuracion: String,
+    pub inicio: String,
+    pub inicio_unix: u64,
+}
+
+impl MetadatosCorrida {
+    pub fn new(semilla: u64) -> Self {
+        let inicio_unix = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        Self {
+            semilla,
+            version: env!("CARGO_PKG_VERSION"),
+            hash_configuracion: hash_configuracion(),
+            inicio: fecha_iso(inicio_unix),
+            inicio_unix,
+        }
+    }
+
+    // Pares (campo, valor) para encabezados de texto
+    fn campos(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("semilla", self.semilla.to_string()),
+            ("version", self.version.to_string()),
+            ("hash_configuracion", self.hash_configuracion.clone()),
+            ("inicio", self.inicio.clone()),
+        ]
+    }
+}
+
+/// FNV-1a de configuraciones.rs y de los archivos de terreno, eventos y escenario que existan;
+/// dos corridas con el mismo hash usaron la misma configuracion
+pub fn hash_configuracion() -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut agregar = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    agregar(include_str!("configuraciones.rs").as_bytes());
+    for ruta in [RUTA_TERRENO, RUTA_GUION_EVENTOS, RUTA_ESCENARIO] {
+        if let Ok(bytes) = fs::read(ruta) {
+            agregar(ruta.as_bytes());
+            agregar(&bytes);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Segundos Unix como fecha y hora UTC en formato ISO 8601
+pub fn fecha_iso(segundos: u64) -> String {
+    let dias = (segundos / 86_400) as i64;
+    let resto = segundos % 86_400;
+    // Dias desde 1970-01-01 a fecha civil (algoritmo de Howard Hinnant)
+    let z = dias + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let dia = doy - (153 * mp + 2) / 5 + 1;
+    let mes = if mp < 10 { mp + 3 } else { mp - 9 };
+    let anio = yoe + era * 400 + if mes <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", anio, mes, dia, resto / 3_600, resto % 3_600 / 60, resto % 60)
+}
+
+// ==================== SALIDAS ====================
+/// Destino de los reportes de una corrida: primero los metadatos, luego filas, al final cerrar.
+/// `vaciar` deja en disco todo lo escrito hasta el momento (se llama una vez por dia).
+/// Es Send para que la simulacion pueda correr dentro de un grupo de hilos de rayon
+pub trait SalidaReportes: Send {
+    fn iniciar(&mut self, metadatos: &MetadatosCorrida) -> Result<(), String>;
+    fn escribir_reporte(&mut self, reporte: &EstadisticasDiarias) -> Result<(), String>;
+    fn escribir_evento(&mut self, evento: &RegistroEvento) -> Result<(), String>;
+    fn escribir_piramide(&mut self, clase: &ClasePiramide) -> Result<(), String>;
+    fn escribir_bitacora(&mut self, registro: &RegistroDepredador) -> Result<(), String>;
+    fn vaciar(&mut self) -> Result<(), String>;
+    fn finalizar(&mut self) -> Result<(), String>;
+}
+
+/// Salida del formato con sus archivos dentro de `directorio`
+pub fn crear_salida(formato: FormatoReportes, directorio: &Path) -> Box<dyn SalidaReportes> {
+    let directorio = directorio.to_path_buf();
+    match formato {
+        FormatoReportes::Csv => Box::new(SalidaCsv { directorio, metadatos: None, reportes: None, eventos: None, bitacora: None, piramide: None }),
+        FormatoReportes::Json => Box::new(SalidaJson {
+            directorio, metadatos: String::new(), reportes: Vec::new(), eventos: Vec::new(), bitacora: Vec::new(), piramide: Vec::new(),
+        }),
+        FormatoReportes::JsonLineas => Box::new(SalidaJsonLineas { directorio, metadatos: Value::Null, reportes: None, eventos: None, bitacora: None, piramide: None }),
+        FormatoReportes::Sqlite => Box::new(SalidaSqlite { directorio, conexion: None, corrida: 0, columnas: HashMap::new() }),
+    }
+}
+
+fn texto_error(e: impl ToString) -> String {
+    e.to_string()
+}
+
+// Archivo <nombre>.<extension> dentro del directorio de la salida
+fn ruta_en(directorio: &Path, nombre: &str, extension: &str) -> PathBuf {
+    directorio.join(format!("{}.{}", nombre, extension))
+}
+
+// CSV: reportes.csv, eventos.csv y bitacora_depredadores.csv, con los metadatos como lineas
+// de comentario '#' al inicio; piramide_edades.csv solo se crea si llega alguna clase
+struct SalidaCsv {
+    directorio: PathBuf,
+    metadatos: Option<MetadatosCorrida>,
+    reportes: Option<csv::Writer<File>>,
+    eventos: Option<csv::Writer<File>>,
+    bitacora: Option<csv::Writer<File>>,
+    piramide: Option<csv::Writer<File>>,
+}
+
+fn csv_con_encabezado(ruta: &Path, metadatos: &MetadatosCorrida) -> Result<csv::Writer<File>, String> {
+    let mut archivo = File::create(ruta).map_err(texto_error)?;
+    for (campo, valor) in metadatos.campos() {
+        writeln!(archivo, "# {}: {}", campo, valor).map_err(texto_error)?;
+    }
+    Ok(csv::Writer::from_writer(archivo))
+}
+
+impl SalidaReportes for SalidaCsv {
+    fn iniciar(&mut self, metadatos: &MetadatosCorrida) -> Result<(), String> {
+        self.reportes = Some(csv_con_encabezado(&ruta_en(&self.directorio, RUTA_REPORTES, "csv"), metadatos)?);
+        self.eventos = Some(csv_con_encabezado(&ruta_en(&self.directorio, RUTA_EVENTOS, "csv"), metadatos)?);
+        self.bitacora = Some(csv_con_encabezado(&ruta_en(&self.directorio, RUTA_BITACORA, "csv"), metadatos)?);
+        self.metadatos = Some(metadatos.clone());
+        Ok(())
+    }
+
+    fn escribir_reporte(&mut self, reporte: &EstadisticasDiarias) -> Result<(), String> {
+        self.reportes.as_mut().ok_or("salida CSV sin iniciar")?.serialize(reporte).map_err(texto_error)
+    }
+
+    fn escribir_evento(&mut self, evento: &RegistroEvento) -> Result<(), String> {
+        self.eventos.as_mut().ok_or("salida CSV sin iniciar")?.serialize(evento).map_err(texto_error)
+    }
+
+    fn escribir_piramide(&mut self, clase: &ClasePiramide) -> Result<(), String> {
+        if self.piramide.is_none() {
+            let metadatos = self.metadatos.as_ref().ok_or("salida CSV sin iniciar")?;
+            self.piramide = Some(csv_con_encabezado(&ruta_en(&self.directorio, RUTA_PIRAMIDE, "csv"), metadatos)?);
+        }
+        self.piramide.as_mut().unwrap().serialize(clase).map_err(texto_error)
+    }
+
+    fn escribir_bitacora(&mut self, registro: &RegistroDepredador) -> Result<(), String> {
+        self.bitacora.as_mut().ok_or("salida CSV sin iniciar")?.serialize(registro).map_err(texto_error)
+    }
+
+    fn vaciar(&mut self) -> Result<(), String> {
+        for w in [&mut self.reportes, &mut self.eventos, &mut self.bitacora, &mut self.piramide].into_iter().flatten() {
+            w.flush().map_err(texto_error)?;
+        }
+        Ok(())
+    }
+
+    fn finalizar(&mut self) -> Result<(), String> {
+        self.vaciar()
+    }
+}
+
+// JSON: un solo documento con metadatos, reportes, eventos, bitacora de depredadores y,
+// si la hay, la piramide de edades. Se reescribe entero cada dia (una fila por linea, ya
+// serializada al llegar) en un archivo temporal que luego se renombra: un corte deja el
+// documento completo del ultimo dia. JSON Lines escribe cada fila al llegar
+struct SalidaJson {
+    directorio: PathBuf,
+    metadatos: String,
+    reportes: Vec<String>,
+    eventos: Vec<String>,
+    bitacora: Vec<String>,
+    piramide: Vec<String>,
+}
+
+impl SalidaReportes for SalidaJson {
+    fn iniciar(&mut self, metadatos: &MetadatosCorrida) -> Result<(), String> {
+        self.metadatos = serde_json::to_string(metadatos).map_err(texto_error)?;
+        self.volcar()
+    }
+
+    fn escribir_reporte(&mut self, reporte: &EstadisticasDiarias) -> Result<(), String> {
+        self.reportes.push(serde_json::to_string(reporte).map_err(texto_error)?);
+        Ok(())
+    }
+
+    fn escribir_evento(&mut self, evento: &RegistroEvento) -> Result<(), String> {
+        self.eventos.push(serde_json::to_string(evento).map_err(texto_error)?);
+        Ok(())
+    }
+
+    fn escribir_piramide(&mut self, clase: &ClasePiramide) -> Result<(), String> {
+        self.piramide.push(serde_json::to_string(clase).map_err(texto_error)?);
+        Ok(())
+    }
+
+    fn escribir_bitacora(&mut self, registro: &RegistroDepredador) -> Result<(), String> {
+        self.bitacora.push(serde_json::to_string(registro).map_err(texto_error)?);
+        Ok(())
+    }
+
+    fn vaciar(&mut self) -> Result<(), String> {
+        self.volcar()
+    }
+
+    fn finalizar(&mut self) -> Result<(), String> {
+        self.volcar()
+    }
+}
+
+impl SalidaJson {
+    fn volcar(&self) -> Result<(), String> {
+        let ruta = ruta_en(&self.directorio, RUTA_REPORTES, "json");
+        let temporal = ruta_en(&self.directorio, RUTA_REPORTES, "json.tmp");
+        let mut archivo = BufWriter::new(File::create(&temporal).map_err(texto_error)?);
+        let mut secciones = vec![("reportes", &self.reportes), ("eventos", &self.eventos), ("bitacora_depredadores", &self.bitacora)];
+        if !self.piramide.is_empty() {
+            secciones.push(("piramide_edades", &self.piramide));
+        }
+        (|| {
+            write!(archivo, "{{\n  \"metadatos\": {}", self.metadatos)?;
+            for (clave, filas) in secciones {
+                write!(archivo, ",\n  \"{}\": [", clave)?;
+                for (i, fila) in filas.iter().enumerate() {
+                    write!(archivo, "{}\n    {}", if i == 0 { "" } else { "," }, fila)?;
+                }
+                write!(archivo, "{}]", if filas.is_empty() { "" } else { "\n  " })?;
+            }
+            writeln!(archivo, "\n}}")
+        })().map_err(texto_error)?;
+        archivo.into_inner().map_err(texto_error)?.sync_all().map_err(texto_error)?;
+        fs::rename(&temporal, &ruta).map_err(texto_error)
+    }
+}
+
+// JSON Lines: un objeto por linea en reportes.jsonl, eventos.jsonl y bitacora_depredadores.jsonl;
+// la primera linea es {"metadatos": ...}. piramide_edades.jsonl solo se crea si llega alguna clase
+struct SalidaJsonLineas {
+    directorio: PathBuf,
+    metadatos: Value,
+    reportes: Option<BufWriter<File>>,
+    eventos: Option<BufWriter<File>>,
+    bitacora: Option<BufWriter<File>>,
+    piramide: Option<BufWriter<File>>,
+}
+
+fn escribir_linea(w: &mut Option<BufWriter<File>>, valor: &impl Serialize) -> Result<(), String> {
+    let w = w.as_mut().ok_or("salida JSON Lines sin iniciar")?;
+    serde_json::to_writer(&mut *w, valor).map_err(texto_error)?;
+    writeln!(w).map_err(texto_error)
+}
+
+impl SalidaReportes for SalidaJsonLineas {
+    fn iniciar(&mut self, metadatos: &MetadatosCorrida) -> Result<(), String> {
+        self.reportes = Some(BufWriter::new(File::create(ruta_en(&self.directorio, RUTA_REPORTES, "jsonl")).map_err(texto_error)?));
+        self.eventos = Some(BufWriter::new(File::create(ruta_en(&self.directorio, RUTA_EVENTOS, "jsonl")).map_err(texto_error)?));
+        self.bitacora = Some(BufWriter::new(File::create(ruta_en(&self.directorio, RUTA_BITACORA, "jsonl")).map_err(texto_error)?));
+        self.metadatos = serde_json::json!({ "metadatos": metadatos });
+        escribir_linea(&mut self.reportes, &self.metadatos)?;
+        escribir_linea(&mut self.eventos, &self.metadatos)?;
+        escribir_linea(&mut self.bitacora, &self.metadatos)
+    }
+
+    fn escribir_reporte(&mut self, reporte: &EstadisticasDiarias) -> Result<(), String> {
+        escribir_linea(&mut self.reportes, reporte)
+    }
+
+    fn escribir_evento(&mut self, evento: &RegistroEvento) -> Result<(), String> {
+        escribir_linea(&mut self.eventos, evento)
+    }
+
+    fn escribir_piramide(&mut self, clase: &ClasePiramide) -> Result<(), String> {
+        if self.piramide.is_none() {
+            self.piramide = Some(BufWriter::new(File::create(ruta_en(&self.directorio, RUTA_PIRAMIDE, "jsonl")).map_err(texto_error)?));
+            escribir_linea(&mut self.piramide, &self.metadatos)?;
+        }
+        escribir_linea(&mut self.piramide, clase)
+    }
+
+    fn escribir_bitacora(&mut self, registro: &RegistroDepredador) -> Result<(), String> {
+        escribir_linea(&mut self.bitacora, registro)
+    }
+
+    fn vaciar(&mut self) -> Result<(), String> {
+        for w in [&mut self.reportes, &mut self.eventos, &mut self.bitacora, &mut self.piramide].into_iter().flatten() {
+            w.flush().map_err(texto_error)?;
+        }
+        Ok(())
+    }
+
+    fn finalizar(&mut self) -> Result<(), String> {
+        self.vaciar()
+    }
+}
+
+// SQLite: tablas corridas, reportes, eventos, bitacora_depredadores y piramide_edades relacionadas por el id de corrida.
+// Las corridas se acumulan en la misma base; las columnas salen de los campos serializados
+// y se agregan con ALTER TABLE si una version nueva trae campos nuevos (en el orden del
+// struct: serde_json conserva el orden de los campos con preserve_order).
+struct SalidaSqlite {
+    directorio: PathBuf,
+    conexion: Option<Connection>,
+    corrida: i64,
+    columnas: HashMap<&'static str, HashSet<String>>,
+}
+
+fn valor_sql(v: &Value) -> rusqlite::types::Value {
+    use rusqlite::types::Value as Sql;
+    match v {
+        Value::Null => Sql::Null,
+        Value::Bool(b) => Sql::Integer(*b as i64),
+        Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => Sql::Integer(i),
+            (None, Some(f)) if n.is_f64() => Sql::Real(f),
+            _ => Sql::Text(n.to_string()), // u64 fuera de rango de i64 (p. ej. semillas)
+        },
+        Value::String(s) => Sql::Text(s.clone()),
+        otro => Sql::Text(otro.to_string()),
+    }
+}
+
+fn tipo_sql(v: &Value) -> &'static str {
+    match v {
+        Value::Number(n) if n.is_f64() => "REAL",
+        Value::Number(n) if n.is_i64() => "INTEGER",
+        Value::Bool(_) => "INTEGER",
+        _ => "TEXT",
+    }
+}
+
+impl SalidaSqlite {
+    // Inserta un objeto serializado en la tabla, creando las columnas que falten
+    fn insertar(&mut self, tabla: &'static str, fila: &impl Serialize) -> Result<(), String> {
+        let conexion = self.conexion.as_ref().ok_or("salida SQLite sin iniciar")?;
+        let Value::Object(campos) = serde_json::to_value(fila).map_err(texto_error)? else {
+            return Err(format!("la fila de {} no es un objeto", tabla));
+        };
+
+        if !self.columnas.contains_key(tabla) {
+            let clave = if tabla == "corridas" {
+                "id INTEGER PRIMARY KEY AUTOINCREMENT"
+            } else {
+                "corrida INTEGER NOT NULL REFERENCES corridas(id)"
+            };
+            conexion.execute(&format!("CREATE TABLE IF NOT EXISTS {} ({})", tabla, clave), []).map_err(texto_error)?;
+            let mut consulta = conexion.prepare(&format!("PRAGMA table_info({})", tabla)).map_err(texto_error)?;
+            let existentes = consulta.query_map([], |f| f.get::<_, String>(1)).map_err(texto_error)?
+                .collect::<Result<HashSet<String>, _>>().map_err(texto_error)?;
+            self.columnas.insert(tabla, existentes);
+        }
+        let conocidas = self.columnas.get_mut(tabla).unwrap();
+        for (nombre, valor) in &campos {
+            if !conocidas.contains(nombre) {
+                conexion.execute(&format!("ALTER TABLE {} ADD COLUMN \"{}\" {}", tabla, nombre, tipo_sql(valor)), [])
+                    .map_err(texto_error)?;
+                conocidas.insert(nombre.clone());
+            }
+        }
+
+        let mut nombres: Vec<String> = campos.keys().map(|k| format!("\"{}\"", k)).collect();
+        let mut valores: Vec<rusqlite::types::Value> = campos.values().map(valor_sql).collect();
+        if tabla != "corridas" {
+            nombres.insert(0, "corrida".to_string());
+            valores.insert(0, rusqlite::types::Value::Integer(self.corrida));
+        }
+        let marcas = vec!["?"; valores.len()].join(", ");
+        conexion.execute(
+            &format!("INSERT INTO {} ({}) VALUES ({})", tabla, nombres.join(", "), marcas),
+            rusqlite::params_from_iter(valores),
+        ).map_err(texto_error)?;
+        Ok(())
+    }
+}
+
+impl SalidaReportes for SalidaSqlite {
+    fn iniciar(&mut self, metadatos: &MetadatosCorrida) -> Result<(), String> {
+        let conexion = Connection::open(self.directorio.join(RUTA_BASE_DATOS)).map_err(texto_error)?;
+        conexion.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;").map_err(texto_error)?;
+        self.conexion = Some(conexion);
+        self.insertar("corridas", metadatos)?;
+        self.corrida = self.conexion.as_ref().unwrap().last_insert_rowid();
+        Ok(())
+    }
+
+    fn escribir_reporte(&mut self, reporte: &EstadisticasDiarias) -> Result<(), String> {
+        self.insertar("reportes", reporte)
+    }
+
+    fn escribir_evento(&mut self, evento: &RegistroEvento) -> Result<(), String> {
+        self.insertar("eventos", evento)
+    }
+
+    fn escribir_piramide(&mut self, clase: &ClasePiramide) -> Result<(), String> {
+        self.insertar("piramide_edades", clase)
+    }
+
+    fn escribir_bitacora(&mut self, registro: &RegistroDepredador) -> Result<(), String> {
+        self.insertar("bitacora_depredadores", registro)
+    }
+
+    // Cada fila se confirma al insertarla
+    fn vaciar(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn finalizar(&mut self) -> Result<(), String> {
+        if let Some(conexion) = self.conexion.take() {
+            conexion.close().map_err(|(_, e)| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sistemas::simulacion::Simulacion;
+    use crate::utilidades::configuraciones::PASO_SIN_INTERFAZ;
+    use crate::utilidades::csv::cargar_reportes_csv;
+
+    // Directorio vacio y propio de la prueba dentro del temporal del sistema
+    fn directorio_prueba(nombre: &str) -> PathBuf {
+        let directorio = std::env::temp_dir().join(format!("salidas_{}_{}", nombre, std::process::id()));
+        let _ = fs::remove_dir_all(&directorio);
+        fs::create_dir_all(&directorio).unwrap();
+        directorio
+    }
+
+    // Tres dias con valores distintos en conteos, decimales y texto
+    fn reportes_prueba() -> Vec<EstadisticasDiarias> {
+        (1..=3).map(|dia| EstadisticasDiarias {
+            dia,
+            conteo_conejos: 10 + dia as usize,
+            conteo_total: 30 + dia as usize,
+            muertes_por_predacion: dia * 2,
+            reserva_total_depredadores: 0.1 * dia as f32,
+            estacion: ["primavera", "verano", "otono"][dia as usize - 1].to_string(),
+            capturas_emboscada: dia,
+            edad_media_conejos: 12.75 + dia as f32,
+            ..Default::default()
+        }).collect()
+    }
+
+    // Escribe los reportes de prueba con la salida del formato y la cierra
+    fn escribir(formato: FormatoReportes, directorio: &Path) -> MetadatosCorrida {
+        let metadatos = MetadatosCorrida::new(42);
+        let mut salida = crear_salida(formato, directorio);
+        salida.iniciar(&metadatos).unwrap();
+        for r in &reportes_prueba() {
+            salida.escribir_reporte(r).unwrap();
+        }
+        salida.vaciar().unwrap();
+        salida.finalizar().unwrap();
+        metadatos
+    }
+
+    // EstadisticasDiarias no compara; se comparan serializadas
+    fn assert_mismos(leidos: &[EstadisticasDiarias], escritos: &[EstadisticasDiarias]) {
+        let texto = |rs: &[EstadisticasDiarias]| rs.iter().map(|r| serde_json::to_string(r).unwrap()).collect::<Vec<_>>();
+        assert_eq!(texto(leidos), texto(escritos));
+    }
+
+    #[test]
+    fn csv_ida_y_vuelta() {
+        let directorio = directorio_prueba("csv");
+        let metadatos = escribir(FormatoReportes::Csv, &directorio);
+        let ruta = ruta_en(&directorio, RUTA_REPORTES, "csv");
+        let texto = fs::read_to_string(&ruta).unwrap();
+        assert!(texto.starts_with("# semilla: 42\n"));
+        assert!(texto.contains(&format!("# hash_configuracion: {}\n", metadatos.hash_configuracion)));
+        assert_mismos(&cargar_reportes_csv(ruta.to_str().unwrap()).unwrap(), &reportes_prueba());
+        let _ = fs::remove_dir_all(&directorio);
+    }
+
+    #[test]
+    fn json_ida_y_vuelta() {
+        let directorio = directorio_prueba("json");
+        escribir(FormatoReportes::Json, &directorio);
+        let texto = fs::read_to_string(ruta_en(&directorio, RUTA_REPORTES, "json")).unwrap();
+        let mut documento: Value = serde_json::from_str(&texto).unwrap();
+        assert_eq!(documento["metadatos"]["semilla"], 42);
+        assert_eq!(documento["eventos"], Value::Array(Vec::new()));
+        assert!(documento.get("piramide_edades").is_none());
+        let leidos: Vec<EstadisticasDiarias> = serde_json::from_value(documento["reportes"].take()).unwrap();
+        assert_mismos(&leidos, &reportes_prueba());
+        let _ = fs::remove_dir_all(&directorio);
+    }
+
+    #[test]
+    fn jsonl_ida_y_vuelta() {
+        let directorio = directorio_prueba("jsonl");
+        escribir(FormatoReportes::JsonLineas, &directorio);
+        let texto = fs::read_to_string(ruta_en(&directorio, RUTA_REPORTES, "jsonl")).unwrap();
+        let mut lineas = texto.lines();
+        let encabezado: Value = serde_json::from_str(lineas.next().unwrap()).unwrap();
+        assert_eq!(encabezado["metadatos"]["semilla"], 42);
+        let leidos: Vec<EstadisticasDiarias> = lineas.map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_mismos(&leidos, &reportes_prueba());
+        let _ = fs::remove_dir_all(&directorio);
+    }
+
+    // Filas de una tabla como objetos JSON (columna -> valor), sin la columna de corrida
+    fn filas_sqlite(conexion: &Connection, tabla: &str) -> Vec<Value> {
+        let mut consulta = conexion.prepare(&format!("SELECT * FROM {} ORDER BY rowid", tabla)).unwrap();
+        let nombres: Vec<String> = consulta.column_names().iter().map(|n| n.to_string()).collect();
+        consulta.query_map([], |f| {
+            let mut fila = serde_json::Map::new();
+            for (i, nombre) in nombres.iter().enumerate().filter(|(_, n)| *n != "corrida") {
+                use rusqlite::types::Value as Sql;
+                let valor = match f.get::<_, Sql>(i)? {
+                    Sql::Null => Value::Null,
+                    Sql::Integer(n) => Value::from(n),
+                    Sql::Real(x) => Value::from(x),
+                    Sql::Text(s) => Value::from(s),
+                    Sql::Blob(_) => unreachable!(),
+                };
+                fila.insert(nombre.clone(), valor);
+            }
+            Ok(Value::Object(fila))
+        }).unwrap().collect::<Result<_, _>>().unwrap()
+    }
+
+    fn columnas_sqlite(conexion: &Connection, tabla: &str) -> Vec<String> {
+        let mut consulta = conexion.prepare(&format!("PRAGMA table_info({})", tabla)).unwrap();
+        consulta.query_map([], |f| f.get::<_, String>(1)).unwrap().collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn sqlite_ida_y_vuelta() {
+        let directorio = directorio_prueba("sqlite");
+        escribir(FormatoReportes::Sqlite, &directorio);
+        let conexion = Connection::open(directorio.join(RUTA_BASE_DATOS)).unwrap();
+        let corridas = filas_sqlite(&conexion, "corridas");
+        assert_eq!(corridas.len(), 1);
+        assert_eq!(corridas[0]["semilla"], 42);
+        let leidos: Vec<EstadisticasDiarias> = filas_sqlite(&conexion, "reportes").into_iter()
+            .map(|f| serde_json::from_value(f).unwrap())
+            .collect();
+        assert_mismos(&leidos, &reportes_prueba());
+        drop(conexion);
+        let _ = fs::remove_dir_all(&directorio);
+    }
+
+    // Una base de una version anterior (menos columnas) recibe las nuevas al final y en el orden
+    // del struct; las filas viejas quedan con NULL en ellas
+    #[test]
+    fn sqlite_agrega_columnas_nuevas_en_orden() {
+        let directorio = directorio_prueba("sqlite_columnas");
+        let metadatos = MetadatosCorrida::new(1);
+        let mut vieja = SalidaSqlite { directorio: directorio.clone(), conexion: None, corrida: 0, columnas: HashMap::new() };
+        vieja.iniciar(&metadatos).unwrap();
+        vieja.insertar("reportes", &serde_json::json!({ "dia": 1, "conteo_total": 5 })).unwrap();
+        drop(vieja);
+
+        let mut nueva = crear_salida(FormatoReportes::Sqlite, &directorio);
+        nueva.iniciar(&metadatos).unwrap();
+        let reporte = &reportes_prueba()[0];
+        nueva.escribir_reporte(reporte).unwrap();
+        drop(nueva);
+
+        let Value::Object(campos) = serde_json::to_value(reporte).unwrap() else { unreachable!() };
+        let mut esperadas = vec!["corrida".to_string(), "dia".to_string(), "conteo_total".to_string()];
+        esperadas.extend(campos.keys().filter(|k| *k != "dia" && *k != "conteo_total").cloned());
+        let conexion = Connection::open(directorio.join(RUTA_BASE_DATOS)).unwrap();
+        assert_eq!(columnas_sqlite(&conexion, "reportes"), esperadas);
+        let filas = filas_sqlite(&conexion, "reportes");
+        assert_eq!(filas[0]["conteo_total"], 5);
+        assert_eq!(filas[0]["conteo_conejos"], Value::Null);
+        assert_eq!(filas[1]["conteo_conejos"], 11);
+        drop(conexion);
+        let _ = fs::remove_dir_all(&directorio);
+    }
+
+    #[test]
+    fn hash_de_configuracion_estable() {
+        let hash = hash_configuracion();
+        assert_eq!(hash.len(), 16);
+        assert_eq!(hash, hash_configuracion());
+        assert_eq!(MetadatosCorrida::new(1).hash_configuracion, MetadatosCorrida::new(2).hash_configuracion);
+    }
+
+    // Un corte a mitad de corrida (sin cerrar la salida) deja en reportes.json todos los dias ya reportados
+    #[test]
+    fn json_se_vuelca_cada_dia() {
+        let directorio = directorio_prueba("json_diario");
+        let mut sim = Simulacion::new(3);
+        sim.abrir_salida_en(FormatoReportes::Json, &directorio).unwrap();
+        while sim.dias < 5 {
+            sim.paso(PASO_SIN_INTERFAZ);
+        }
+        let texto = fs::read_to_string(ruta_en(&directorio, RUTA_REPORTES, "json")).unwrap();
+        let documento: Value = serde_json::from_str(&texto).unwrap();
+        assert_eq!(documento["metadatos"]["semilla"], 3);
+        let esperados: Vec<Value> = sim.reportes.iter().map(|r| serde_json::from_str(&serde_json::to_string(r).unwrap()).unwrap()).collect();
+        assert_eq!(esperados.len(), 5);
+        assert_eq!(documento["reportes"], Value::Array(esperados));
+        drop(sim);
+        let _ = fs::remove_dir_all(&directorio);
+    }
+}